@@ -1,8 +1,8 @@
-use std::collections::HashSet;
-use swc_core::common::DUMMY_SP;
+use swc_core::common::{DUMMY_SP, Span, Spanned};
 use swc_core::ecma::ast::{*};
 use swc_core::ecma::atoms::JsWord;
 use swc_core::ecma::utils::quote_ident;
+use crate::options::RuntimeExport;
 
 pub fn get_jsx_attr<'a>(el: &'a JSXOpeningElement, name: &str) -> Option<&'a JSXAttr> {
     for attr in &el.attrs {
@@ -42,33 +42,35 @@ pub fn get_jsx_attr_value_as_string(val: &JSXAttrValue) -> Option<String> {
     }
 }
 
-pub fn pick_jsx_attrs(mut attrs: Vec<JSXAttrOrSpread>, names: HashSet<&str>) -> Vec<JSXAttrOrSpread> {
+/// Keep attributes matched by predicate and all spreads in their original order,
+/// namespaced attributes are matched by the full name, e.g. `xml:lang`
+pub fn pick_jsx_attrs<F: Fn(&str) -> bool>(mut attrs: Vec<JSXAttrOrSpread>, predicate: F) -> Vec<JSXAttrOrSpread> {
     attrs.retain(|attr| {
-        if let JSXAttrOrSpread::JSXAttr(attr) = attr {
-            if let JSXAttrName::Ident(ident) = &attr.name {
-                let name: &str = &ident.sym.to_string();
-                if let Some(_) = names.get(name) {
-                    return true;
-                }
+        match attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), .. }) => {
+                predicate(&ident.sym)
             }
+            JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::JSXNamespacedName(name), .. }) => {
+                predicate(&format!("{}:{}", name.ns.sym, name.name.sym))
+            }
+            JSXAttrOrSpread::SpreadElement(_) => true,
         }
-        return false;
     });
 
     attrs
 }
 
 
-pub fn create_jsx_attribute(name: &str, exp: Box<Expr>) -> JSXAttrOrSpread {
+pub fn create_jsx_attribute(name: &str, exp: Box<Expr>, span: Span) -> JSXAttrOrSpread {
     JSXAttrOrSpread::JSXAttr(JSXAttr {
-        span: DUMMY_SP,
+        span,
         name: JSXAttrName::Ident(Ident {
-            span: DUMMY_SP,
+            span,
             sym: name.into(),
             optional: false,
         }),
         value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
-            span: DUMMY_SP,
+            span,
             expr: JSXExpr::Expr(exp),
         })),
     })
@@ -136,23 +138,143 @@ pub fn match_prop_key(prop: &KeyValueProp, name: &str) -> bool {
 pub fn create_key_value_prop(key: &str, value: Box<Expr>) -> PropOrSpread {
     return PropOrSpread::Prop(Box::new(Prop::KeyValue(
         KeyValueProp {
-            key: PropName::Ident(quote_ident!(key)),
+            key: PropName::Ident(quote_ident!(value.span(), key)),
             value,
         }
     )));
 }
 
-pub fn create_import(source: JsWord, specifier: Ident) -> ModuleItem {
+/// Boolean literal, negation or comparison, which always evaluates to a boolean,
+/// or `&&` and `||` of such operands
+pub fn is_boolean_expr(exp: &Expr) -> bool {
+    match exp {
+        Expr::Bin(BinExpr { op: BinaryOp::LogicalAnd | BinaryOp::LogicalOr, left, right, .. }) => {
+            is_boolean_expr(left) && is_boolean_expr(right)
+        }
+        Expr::Lit(Lit::Bool(_))
+        | Expr::Unary(UnaryExpr { op: UnaryOp::Bang, .. })
+        | Expr::Bin(BinExpr {
+            op: BinaryOp::EqEq | BinaryOp::NotEq | BinaryOp::EqEqEq | BinaryOp::NotEqEq
+            | BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq
+            | BinaryOp::In | BinaryOp::InstanceOf,
+            ..
+        }) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_boolean_expr(expr),
+        _ => false,
+    }
+}
+
+/// Cast expression to boolean with `!!`, unless it's already a boolean
+pub fn create_boolean_cast(exp: Box<Expr>) -> Box<Expr> {
+    if is_boolean_expr(&exp) {
+        return exp;
+    }
+
+    let span = exp.span();
+    let arg = match exp.as_ref() {
+        Expr::Ident(_) | Expr::Member(_) | Expr::Call(_) | Expr::Paren(_) => exp,
+        _ => Box::new(Expr::Paren(ParenExpr { span, expr: exp })),
+    };
+
+    Box::new(Expr::Unary(UnaryExpr {
+        span,
+        op: UnaryOp::Bang,
+        arg: Box::new(Expr::Unary(UnaryExpr {
+            span,
+            op: UnaryOp::Bang,
+            arg,
+        })),
+    }))
+}
+
+/// Value of the directive prologue item, e.g. "use client"
+pub fn get_directive(item: &ModuleItem) -> Option<&JsWord> {
+    if let ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) = item {
+        if let Expr::Lit(Lit::Str(str)) = expr.as_ref() {
+            return Some(&str.value);
+        }
+    }
+
+    None
+}
+
+pub fn create_import_specifier(local: Ident, export: &RuntimeExport) -> ImportSpecifier {
+    match export {
+        RuntimeExport::Named(name) => ImportSpecifier::Named(ImportNamedSpecifier {
+            span: DUMMY_SP,
+            imported: if &local.sym == name { None } else { Some(ModuleExportName::Ident(quote_ident!(&name[..]))) },
+            local,
+            is_type_only: false,
+        }),
+        RuntimeExport::Default => ImportSpecifier::Default(ImportDefaultSpecifier {
+            span: DUMMY_SP,
+            local,
+        }),
+        RuntimeExport::Namespace(_) => ImportSpecifier::Namespace(ImportStarAsSpecifier {
+            span: DUMMY_SP,
+            local,
+        }),
+    }
+}
+
+/// Find a local binding of the runtime export in the import declaration, aliased or not
+/// import { i18n as core } from "@lingui/core" -> core
+pub fn find_import_local(imp: &ImportDecl, export: &RuntimeExport) -> Option<Id> {
+    if imp.type_only {
+        return None;
+    }
+
+    imp.specifiers.iter().find_map(|spec| match (spec, export) {
+        (ImportSpecifier::Named(spec), RuntimeExport::Named(_) | RuntimeExport::Default) if !spec.is_type_only => {
+            let imported = match &spec.imported {
+                Some(ModuleExportName::Ident(ident)) => &ident.sym,
+                Some(ModuleExportName::Str(str)) => &str.value,
+                None => &spec.local.sym,
+            };
+
+            let name = match export {
+                RuntimeExport::Named(name) => name.as_str(),
+                _ => "default",
+            };
+
+            (imported == name).then(|| spec.local.to_id())
+        }
+        (ImportSpecifier::Default(spec), RuntimeExport::Default) => Some(spec.local.to_id()),
+        (ImportSpecifier::Namespace(spec), RuntimeExport::Namespace(_)) => Some(spec.local.to_id()),
+        _ => None
+    })
+}
+
+/// Could the runtime export be added to the import declaration?
+/// `import * as core from "..."` could not be combined with named imports,
+/// and namespace import is always added as a separate declaration
+pub fn is_extendable_import(imp: &ImportDecl, export: &RuntimeExport) -> bool {
+    if imp.type_only {
+        return false;
+    }
+
+    match export {
+        RuntimeExport::Named(_) => !imp.specifiers.iter().any(|spec| matches!(spec, ImportSpecifier::Namespace(_))),
+        RuntimeExport::Default => !imp.specifiers.iter().any(|spec| matches!(spec, ImportSpecifier::Default(_))),
+        RuntimeExport::Namespace(_) => false,
+    }
+}
+
+/// Add specifier to an existing import declaration, default specifier always goes first
+pub fn add_import_specifier(item: &mut ModuleItem, specifier: ImportSpecifier) {
+    if let ModuleItem::ModuleDecl(ModuleDecl::Import(imp)) = item {
+        if let ImportSpecifier::Default(_) = specifier {
+            imp.specifiers.insert(0, specifier);
+        } else {
+            imp.specifiers.push(specifier);
+        }
+    }
+}
+
+pub fn create_import(source: JsWord, specifier: ImportSpecifier, span: Span) -> ModuleItem {
     ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-        span: DUMMY_SP,
-        specifiers: vec![
-            ImportSpecifier::Named(ImportNamedSpecifier {
-                span: DUMMY_SP,
-                local: specifier,
-                imported: None,
-                is_type_only: false,
-            })
-        ],
+        span,
+        specifiers: vec![specifier],
         src: Box::new(Str {
             span: DUMMY_SP,
             value: source,
@@ -162,3 +284,21 @@ pub fn create_import(source: JsWord, specifier: Ident) -> ModuleItem {
         type_only: false,
     }))
 }
+
+/// export const name = [...];
+pub fn create_const_export(name: &str, init: Box<Expr>) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        span: DUMMY_SP,
+        decl: Decl::Var(Box::new(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(BindingIdent::from(quote_ident!(name))),
+                init: Some(init),
+                definite: false,
+            }],
+        })),
+    }))
+}
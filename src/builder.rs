@@ -1,6 +1,8 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
 use swc_core::{
-    common::{DUMMY_SP},
+    common::{Span, Spanned},
     ecma::{
         ast::*,
     },
@@ -11,6 +13,8 @@ use crate::{
     normalize_witespaces_jsx::normalize_whitespaces_jsx
 };
 use crate::tokens::{IcuChoice, CaseOrOffset, MsgToken};
+use crate::options::{BidiIsolation, LinguiOptions, PseudoLocalization};
+use crate::pseudo;
 
 fn dedup_values(mut v: Vec<ValueWithPlaceholder>) -> Vec<ValueWithPlaceholder> {
     let mut uniques = HashSet::new();
@@ -26,7 +30,7 @@ pub struct ValueWithPlaceholder {
 
 impl ValueWithPlaceholder {
     pub fn to_prop(self) -> PropOrSpread {
-        let ident = Ident::new(self.placeholder.into(), DUMMY_SP);
+        let ident = Ident::new(self.placeholder.into(), self.value.span());
 
         PropOrSpread::Prop(Box::new(
             Prop::KeyValue(KeyValueProp {
@@ -39,28 +43,47 @@ impl ValueWithPlaceholder {
 
 pub struct MessageBuilderResult {
     pub message: Box<Expr>,
+    /// message shown when rendered from the source: accented and expanded by pseudo localization
+    /// or with isolated placeholders, the original message is kept as ID
+    pub display_message: Option<Box<Expr>>,
     pub values: Option<Box<Expr>>,
     pub components: Option<Box<Expr>>,
 }
 
 pub struct MessageBuilder {
+    /// span of the macro, generated nodes are mapped to it
+    span: Span,
     message: String,
+    /// the message with accented text and isolated placeholders, built along with the original one
+    display_message: String,
 
     components_stack: Vec<usize>,
     components: Vec<ValueWithPlaceholder>,
 
     values: Vec<ValueWithPlaceholder>,
     values_indexed: Vec<ValueWithPlaceholder>,
+
+    pseudo: Option<PseudoLocalization>,
+    bidi_isolation: Option<BidiIsolation>,
+    normalize_unicode: bool,
+    /// count of characters in the literal text, used for pseudo localization padding
+    text_len: usize,
 }
 
 impl MessageBuilder {
-    pub fn parse(tokens: Vec<MsgToken>, jsx: bool) -> MessageBuilderResult {
+    pub fn parse(tokens: Vec<MsgToken>, jsx: bool, span: Span, options: &LinguiOptions) -> MessageBuilderResult {
         let mut builder = MessageBuilder {
+            span,
             message: String::new(),
+            display_message: String::new(),
             components_stack: Vec::new(),
             components: Vec::new(),
             values: Vec::new(),
             values_indexed: Vec::new(),
+            pseudo: options.pseudo_localization.clone(),
+            bidi_isolation: options.bidi_isolation.clone(),
+            normalize_unicode: options.normalize_unicode,
+            text_len: 0,
         };
 
         builder.from_tokens(tokens);
@@ -68,34 +91,50 @@ impl MessageBuilder {
     }
 
     pub fn to_args(mut self, jsx: bool) -> MessageBuilderResult {
-        let message = Box::new(Expr::Lit(Lit::Str(Str {
-            span: DUMMY_SP,
-            value: if jsx {
-                normalize_whitespaces_jsx(&self.message).into()
-            } else {
-                normalize_whitespaces_js(&self.message).into()
-            },
+        let normalize = |message: &str| if jsx {
+            normalize_whitespaces_jsx(message)
+        } else {
+            normalize_whitespaces_js(message)
+        };
+
+        let span = self.span;
+        let create_str = |value: String| Box::new(Expr::Lit(Lit::Str(Str {
+            span,
+            value: value.into(),
             raw: None,
         })));
 
+        let message = normalize(&self.message);
+        let display_message = normalize(&self.display_message);
+
+        let display_message = match &self.pseudo {
+            Some(pseudo) if self.text_len > 0 => Some(pseudo::wrap(&display_message, self.text_len, pseudo)),
+            // messages without text, e.g. "{name}", are not pseudo localized
+            _ if self.bidi_isolation.is_some() && display_message != message => Some(display_message),
+            _ => None,
+        }.map(create_str);
+
+        let message = create_str(message);
+
         self.values.append(&mut self.values_indexed);
 
         let values = if self.values.len() > 0 {
             Some(Box::new(Expr::Object(ObjectLit {
-                span: DUMMY_SP,
+                span: self.span,
                 props: dedup_values(self.values).into_iter().map(|item| item.to_prop()).collect(),
             })))
         } else { None };
 
         let components = if self.components.len() > 0 {
             Some(Box::new(Expr::Object(ObjectLit {
-                span: DUMMY_SP,
+                span: self.span,
                 props: self.components.into_iter().map(|item| item.to_prop()).collect(),
             })))
         } else { None };
 
         MessageBuilderResult {
             message,
+            display_message,
             values,
             components,
         }
@@ -105,12 +144,12 @@ impl MessageBuilder {
         for token in tokens {
             match token {
                 MsgToken::String(str) => {
-                    self.push_msg(&str);
+                    self.push_text(&str);
                 }
 
                 MsgToken::Expression(val) => {
                     let placeholder = self.push_exp(val);
-                    self.push_msg(&format!("{{{placeholder}}}"));
+                    self.push_placeholder(&placeholder);
                 }
 
                 MsgToken::TagOpening(val) => {
@@ -126,8 +165,50 @@ impl MessageBuilder {
         }
     }
 
+    fn has_display_message(&self) -> bool {
+        self.pseudo.is_some() || self.bidi_isolation.is_some()
+    }
+
     fn push_msg(&mut self, val: &str) {
         self.message.push_str(val);
+
+        if self.has_display_message() {
+            self.display_message.push_str(val);
+        }
+    }
+
+    fn push_placeholder(&mut self, placeholder: &str) {
+        self.message.push_str(&format!("{{{placeholder}}}"));
+
+        if !self.has_display_message() {
+            return;
+        }
+
+        // FSI {name} PDI, so the value doesn't reorder the surrounding text in RTL locales
+        if self.bidi_isolation.as_ref().is_some_and(|bidi| !bidi.exclude.iter().any(|name| name == placeholder)) {
+            self.display_message.push_str(&format!("\u{2068}{{{placeholder}}}\u{2069}"));
+        } else {
+            self.display_message.push_str(&format!("{{{placeholder}}}"));
+        }
+    }
+
+    /// Literal text of the message, placeholders, tags and ICU syntax go through `push_msg`
+    fn push_text(&mut self, val: &str) {
+        // the same text typed in NFD and NFC forms should produce the same message and ID
+        let val = if self.normalize_unicode {
+            Cow::Owned(val.nfc().collect::<String>())
+        } else {
+            Cow::Borrowed(val)
+        };
+
+        if self.pseudo.is_some() {
+            self.text_len += pseudo::text_len(&val);
+            self.display_message.push_str(&pseudo::accent(&val));
+        } else if self.bidi_isolation.is_some() {
+            self.display_message.push_str(&val);
+        }
+
+        self.message.push_str(&val);
     }
 
     fn push_tag_opening(&mut self, el: JSXOpeningElement, self_closing: bool) {
@@ -144,10 +225,10 @@ impl MessageBuilder {
             placeholder: self.components.len().to_string(),
             value: Box::new(Expr::JSXElement(
                 Box::new(JSXElement {
+                    span: el.span,
                     opening: el,
                     closing: None,
                     children: vec![],
-                    span: DUMMY_SP,
                 })
             )),
         });
@@ -0,0 +1,32 @@
+use crate::options::DebugMarkers;
+
+/// Zero-width characters used to encode the ID bit by bit
+const ZERO_WIDTH_BITS: [char; 2] = ['\u{200B}', '\u{200C}'];
+/// Word joiner delimits the encoded ID, so tools could find it in the rendered text
+const ZERO_WIDTH_DELIMITER: char = '\u{2060}';
+
+/// Escape ICU syntax characters, so the ID is rendered as is: "a {name}" -> "a '{name}'"
+/// Apostrophe starts a quoted text only before a brace, so the ID is quoted from the first brace.
+/// `#` is a syntax character only inside plural cases, markers are at the top level of the message
+fn escape_icu(text: &str) -> String {
+    match text.find(['{', '}']) {
+        Some(index) => format!("{}'{}'", text[..index].replace('\'', "''"), text[index..].replace('\'', "''")),
+        None => text.replace('\'', "''"),
+    }
+}
+
+/// Prepend the marker carrying ID to the message text
+/// Visible: "⟦greeting⟧ Hello"
+/// Zero width: "⁠<bits of UTF-8 bytes of the ID>⁠Hello"
+pub fn mark(message: &str, id: &str, markers: DebugMarkers) -> String {
+    match markers {
+        DebugMarkers::Visible => format!("⟦{}⟧ {message}", escape_icu(id)),
+        DebugMarkers::ZeroWidth => {
+            let bits: String = id.bytes()
+                .flat_map(|byte| (0..8).rev().map(move |bit| ZERO_WIDTH_BITS[((byte >> bit) & 1) as usize]))
+                .collect();
+
+            format!("{ZERO_WIDTH_DELIMITER}{bits}{ZERO_WIDTH_DELIMITER}{message}")
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use swc_core::{
+    common::{Span, DUMMY_SP},
+    ecma::{
+        ast::*,
+        utils::quote_ident,
+    },
+};
+
+/// Message in Lingui's compiled format, the same as produced by `compileMessage` from `@lingui/message-utils`
+/// "Hello" -> "Hello"
+/// "Hello {name}" -> ["Hello ", ["name"]]
+/// "{count, plural, one {# book} other {# books}}" -> [["count", "plural", { one: ["#", " book"], other: ["#", " books"] }]]
+#[derive(Debug, PartialEq)]
+pub enum CompiledMessage {
+    Text(String),
+    Tokens(Vec<CompiledToken>),
+}
+
+/// Cases of the choice in the source order, "=0" key is "0"
+pub type ChoiceCases = Vec<(String, CompiledMessage)>;
+
+#[derive(Debug, PartialEq)]
+pub enum CompiledToken {
+    Text(String),
+    Octothorpe,
+    /// {name}
+    Argument(String),
+    /// {price, number} or {price, number, ::currency/EUR}
+    Function(String, String, Option<String>),
+    /// {count, plural, offset:1 one {...} other {...}}
+    Choice {
+        arg: String,
+        kind: String,
+        offset: Option<f64>,
+        cases: ChoiceCases,
+    },
+}
+
+struct IcuParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl IcuParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|ch| ch.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(ch) if ch == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(ch) => Err(format!("expected `{expected}`, found `{ch}` at {}", self.pos)),
+            None => Err(format!("expected `{expected}`, found end of message")),
+        }
+    }
+
+    /// Read characters until whitespace or one of the stop characters
+    fn read_word(&mut self, stop: &[char]) -> String {
+        let start = self.pos;
+
+        while self.peek().is_some_and(|ch| !ch.is_whitespace() && !stop.contains(&ch)) {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// Message until the end of input or the closing brace of the enclosing case
+    fn parse_message(&mut self, in_plural: bool, nested: bool) -> Result<Vec<CompiledToken>, String> {
+        let mut tokens: Vec<CompiledToken> = Vec::new();
+        let mut text = String::new();
+
+        loop {
+            match self.peek() {
+                None if nested => return Err("unclosed case of the choice".into()),
+                None => break,
+                Some('}') if nested => break,
+                Some('}') => return Err(format!("unexpected `}}` at {}", self.pos)),
+                Some('{') => {
+                    if !text.is_empty() {
+                        tokens.push(CompiledToken::Text(std::mem::take(&mut text)));
+                    }
+
+                    tokens.push(self.parse_argument(in_plural)?);
+                }
+                Some('#') if in_plural => {
+                    if !text.is_empty() {
+                        tokens.push(CompiledToken::Text(std::mem::take(&mut text)));
+                    }
+
+                    tokens.push(CompiledToken::Octothorpe);
+                    self.pos += 1;
+                }
+                Some('\'') => text.push_str(&self.parse_apostrophe(in_plural)),
+                Some(ch) => {
+                    text.push(ch);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(CompiledToken::Text(text));
+        }
+
+        Ok(tokens)
+    }
+
+    /// '' is a single apostrophe, '{text}' is quoted text with syntax characters, otherwise apostrophe as is
+    fn parse_apostrophe(&mut self, in_plural: bool) -> String {
+        match self.peek_at(1) {
+            Some('\'') => {
+                self.pos += 2;
+                "'".into()
+            }
+            Some('{' | '}') => self.parse_quoted(),
+            Some('#') if in_plural => self.parse_quoted(),
+            _ => {
+                self.pos += 1;
+                "'".into()
+            }
+        }
+    }
+
+    fn parse_quoted(&mut self) -> String {
+        let mut text = String::new();
+        // opening apostrophe
+        self.pos += 1;
+
+        while let Some(ch) = self.peek() {
+            self.pos += 1;
+
+            if ch == '\'' {
+                if self.peek() == Some('\'') {
+                    text.push('\'');
+                    self.pos += 1;
+                } else {
+                    break;
+                }
+            } else {
+                text.push(ch);
+            }
+        }
+
+        text
+    }
+
+    fn parse_argument(&mut self, in_plural: bool) -> Result<CompiledToken, String> {
+        self.expect('{')?;
+        self.skip_whitespace();
+
+        let arg = self.read_word(&['{', '}', ',']);
+
+        if arg.is_empty() {
+            return Err(format!("expected argument name at {}", self.pos));
+        }
+
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(CompiledToken::Argument(arg));
+        }
+
+        self.expect(',')?;
+        self.skip_whitespace();
+
+        let kind = self.read_word(&['{', '}', ',']);
+        self.skip_whitespace();
+
+        match kind.as_str() {
+            "plural" | "selectordinal" | "select" => {
+                self.expect(',')?;
+                let (offset, cases) = self.parse_cases(kind != "select" || in_plural, kind != "select")?;
+
+                Ok(CompiledToken::Choice { arg, kind, offset, cases })
+            }
+            "" => Err(format!("expected argument type at {}", self.pos)),
+            _ if self.peek() == Some(',') => {
+                self.pos += 1;
+                let style = self.read_style()?;
+
+                Ok(CompiledToken::Function(arg, kind, Some(style.trim().into())))
+            }
+            _ => {
+                self.expect('}')?;
+
+                Ok(CompiledToken::Function(arg, kind, None))
+            }
+        }
+    }
+
+    /// Raw style of the formatting function until the closing brace of the argument
+    fn read_style(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        let mut depth = 0;
+
+        loop {
+            match self.peek() {
+                None => return Err("unclosed argument".into()),
+                Some('{') => depth += 1,
+                Some('}') if depth == 0 => break,
+                Some('}') => depth -= 1,
+                _ => {}
+            }
+
+            self.pos += 1;
+        }
+
+        let style = self.chars[start..self.pos].iter().collect();
+        self.pos += 1;
+
+        Ok(style)
+    }
+
+    fn parse_cases(&mut self, in_plural: bool, allow_offset: bool) -> Result<(Option<f64>, ChoiceCases), String> {
+        let mut offset = None;
+        let mut cases = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => return Err("unclosed choice".into()),
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {}
+            }
+
+            let key = self.read_word(&['{', '}']);
+
+            if let Some(value) = key.strip_prefix("offset:").filter(|_| allow_offset && cases.is_empty()) {
+                let value = if value.is_empty() {
+                    self.skip_whitespace();
+                    self.read_word(&['{', '}'])
+                } else {
+                    value.into()
+                };
+
+                offset = Some(value.parse::<f64>().map_err(|_| format!("invalid offset `{value}`"))?);
+                continue;
+            }
+
+            if key.is_empty() {
+                return Err(format!("expected case key at {}", self.pos));
+            }
+
+            self.skip_whitespace();
+            self.expect('{')?;
+            let tokens = self.parse_message(in_plural, true)?;
+            self.expect('}')?;
+
+            // "=0" case is "0" in the compiled message
+            let key = key.strip_prefix('=').map(String::from).unwrap_or(key);
+            cases.push((key, to_compiled_message(tokens)));
+        }
+
+        Ok((offset, cases))
+    }
+}
+
+/// Messages without placeholders are plain strings
+fn to_compiled_message(tokens: Vec<CompiledToken>) -> CompiledMessage {
+    if tokens.iter().all(|token| matches!(token, CompiledToken::Text(_))) {
+        return CompiledMessage::Text(tokens.into_iter().map(|token| match token {
+            CompiledToken::Text(text) => text,
+            _ => unreachable!(),
+        }).collect());
+    }
+
+    CompiledMessage::Tokens(tokens)
+}
+
+pub fn compile_message(message: &str) -> Result<CompiledMessage, String> {
+    let mut parser = IcuParser {
+        chars: message.chars().collect(),
+        pos: 0,
+    };
+
+    let tokens = parser.parse_message(false, false)?;
+
+    Ok(to_compiled_message(tokens))
+}
+
+fn create_str(value: &str, span: Span) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span,
+        value: value.into(),
+        raw: None,
+    }))
+}
+
+fn create_array(elems: Vec<Expr>, span: Span) -> Expr {
+    Expr::Array(ArrayLit {
+        span,
+        elems: elems.into_iter().map(|expr| Some(ExprOrSpread { spread: None, expr: Box::new(expr) })).collect(),
+    })
+}
+
+fn create_prop(key: &str, value: Expr) -> PropOrSpread {
+    let is_ident = key.chars().next().is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '$')
+        && key.chars().all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$');
+
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: if is_ident {
+            PropName::Ident(quote_ident!(key))
+        } else {
+            PropName::Str(key.into())
+        },
+        value: Box::new(value),
+    })))
+}
+
+impl CompiledToken {
+    fn into_expr(self, span: Span) -> Expr {
+        match self {
+            CompiledToken::Text(text) => create_str(&text, span),
+            CompiledToken::Octothorpe => create_str("#", span),
+            CompiledToken::Argument(arg) => create_array(vec![create_str(&arg, span)], span),
+            CompiledToken::Function(arg, kind, style) => {
+                let mut elems = vec![create_str(&arg, span), create_str(&kind, span)];
+                elems.extend(style.map(|style| create_str(&style, span)));
+
+                create_array(elems, span)
+            }
+            CompiledToken::Choice { arg, kind, offset, cases } => {
+                let mut props = Vec::new();
+
+                if let Some(offset) = offset {
+                    props.push(create_prop("offset", Expr::Lit(Lit::Num(Number {
+                        span: DUMMY_SP,
+                        value: offset,
+                        raw: None,
+                    }))));
+                }
+
+                props.extend(cases.into_iter().map(|(key, message)| create_prop(&key, message.into_expr(span))));
+
+                create_array(vec![
+                    create_str(&arg, span),
+                    create_str(&kind, span),
+                    Expr::Object(ObjectLit { span, props }),
+                ], span)
+            }
+        }
+    }
+}
+
+impl CompiledMessage {
+    pub fn into_expr(self, span: Span) -> Expr {
+        match self {
+            CompiledMessage::Text(text) => create_str(&text, span),
+            CompiledMessage::Tokens(tokens) => {
+                create_array(tokens.into_iter().map(|token| token.into_expr(span)).collect(), span)
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use swc_core::{
-    common::DUMMY_SP,
+    common::{Span, Spanned},
     ecma::{
         ast::*,
         utils::ExprFactory,
@@ -22,8 +22,41 @@ impl<'a> JsMacroFolder<'a> {
         }
     }
 
-    fn create_i18n_fn_call_from_tokens(&mut self, callee_obj: Option<Box<Expr>>, tokens: Vec<MsgToken>) -> CallExpr {
-        let parsed = MessageBuilder::parse(tokens, false);
+    /// `span` is a span of the replaced macro call, `msg_span` is a span of the message source
+    fn create_i18n_fn_call_from_tokens(&mut self, callee_obj: Option<Box<Expr>>, tokens: Vec<MsgToken>, span: Span, msg_span: Span) -> CallExpr {
+        let parsed = MessageBuilder::parse(tokens, false, msg_span, &self.ctx.options);
+
+        let generated_id = self.ctx.generated_id(&parsed.message);
+        let origin = self.ctx.create_origin(span);
+
+        self.ctx.record_message_id(generated_id.as_deref().unwrap_or(&parsed.message));
+
+        // i18n._({id: "namespace.message", message: "message", values: {}, origin: [...]})
+        if generated_id.is_some() || origin.is_some() {
+            let mut props = match generated_id {
+                Some(id) if self.ctx.options.should_keep_field("message") => {
+                    let message = self.ctx.compile_message(self.ctx.mark_message(parsed.display_message.unwrap_or(parsed.message), &id));
+
+                    vec![
+                        create_key_value_prop("id", id),
+                        create_key_value_prop("message", message),
+                    ]
+                }
+                Some(id) => vec![create_key_value_prop("id", id)],
+                None => vec![create_key_value_prop("id", parsed.message)],
+            };
+
+            if let Some(v) = parsed.values {
+                props.push(create_key_value_prop("values", v));
+            }
+
+            if let Some(origin) = origin {
+                props.push(create_key_value_prop("origin", origin));
+            }
+
+            let descriptor = Box::new(Expr::Object(ObjectLit { span: msg_span, props }));
+            return self.create_i18n_fn_call(callee_obj, vec![descriptor.as_arg()], span);
+        }
 
         let mut args: Vec<ExprOrSpread> = vec![parsed.message.as_arg()];
 
@@ -31,21 +64,26 @@ impl<'a> JsMacroFolder<'a> {
             args.push(v.as_arg())
         }
 
-        return self.create_i18n_fn_call(callee_obj, args);
+        self.create_i18n_fn_call(callee_obj, args, span)
     }
 
-    fn create_i18n_fn_call(&mut self, callee_obj: Option<Box<Expr>>, args: Vec<ExprOrSpread>) -> CallExpr {
+    fn create_i18n_fn_call(&mut self, callee_obj: Option<Box<Expr>>, args: Vec<ExprOrSpread>, span: Span) -> CallExpr {
+        if let Some(arg) = args.first() {
+            self.ctx.annotate_message(arg.expr.span());
+        }
+
+        self.ctx.annotate_pure(span);
+
         let t = CallExpr {
-            span: DUMMY_SP,
+            span,
             callee: Expr::Member(MemberExpr {
-                span: DUMMY_SP,
+                span,
                 obj: callee_obj.unwrap_or_else(|| {
                     self.ctx.should_add_18n_import = true;
-                    let (_, i18n_export) = &self.ctx.options.runtime_modules.i18n;
 
-                    return Box::new(Ident::new(i18n_export.clone().into(), DUMMY_SP).into());
+                    self.ctx.runtime_i18n_expr(span)
                 }),
-                prop: MemberProp::Ident(Ident::new("_".into(), DUMMY_SP)),
+                prop: MemberProp::Ident(Ident::new("_".into(), span)),
             }).as_callee(),
             args,
             type_args: None,
@@ -55,20 +93,40 @@ impl<'a> JsMacroFolder<'a> {
     }
 
     // take {message: "", id: "", ...} object literal, process message and return updated props
-    fn update_msg_descriptor_props(&self, expr: Box<Expr>) -> Box<Expr> {
+    /// `macro_span` is a span of the macro call, used for origin metadata
+    fn update_msg_descriptor_props(&mut self, expr: Box<Expr>, span: Span, macro_span: Span) -> Box<Expr> {
         if let Expr::Object(obj) = *expr {
             let has_id = has_object_prop(&obj.props, "id");
+            let explicit_id = obj.props.iter()
+                .filter_map(to_key_value_prop)
+                .find(|prop| match_prop_key(prop, "id"))
+                .map(|prop| prop.value.clone());
 
             let mut new_props: Vec<PropOrSpread> = obj.props.into_iter().flat_map(|prop_or_spread| {
                 if let Some(prop) = to_key_value_prop(&prop_or_spread) {
                     if match_prop_key(prop, "message") {
                         let tokens = self.ctx.try_tokenize_expr(&prop.value).unwrap_or_else(|| Vec::new());
 
-                        let parsed = MessageBuilder::parse(tokens, false);
+                        let parsed = MessageBuilder::parse(tokens, false, prop.value.span(), &self.ctx.options);
+                        // new `id` / `message` prop is spanned with the value
+                        self.ctx.move_comments(prop.key.span(), prop.value.span());
 
-                        let mut args: Vec<PropOrSpread> = vec![
-                            create_key_value_prop(if has_id { "message" } else { "id" }, parsed.message),
-                        ];
+                        let mut args: Vec<PropOrSpread> = match (self.ctx.generated_id(&parsed.message), &explicit_id) {
+                            (Some(id), _) if !has_id => {
+                                let message = self.ctx.compile_message(self.ctx.mark_message(parsed.display_message.unwrap_or(parsed.message), &id));
+
+                                vec![
+                                    create_key_value_prop("id", id),
+                                    create_key_value_prop("message", message),
+                                ]
+                            }
+                            (_, Some(id)) => vec![
+                                create_key_value_prop("message", self.ctx.compile_message(self.ctx.mark_message(parsed.display_message.unwrap_or(parsed.message), id))),
+                            ],
+                            _ => vec![
+                                create_key_value_prop(if has_id { "message" } else { "id" }, parsed.message),
+                            ],
+                        };
 
                         if let Some(v) = parsed.values {
                             args.push(
@@ -87,17 +145,20 @@ impl<'a> JsMacroFolder<'a> {
                 new_props = new_props.into_iter().filter(| prop| {
                     to_key_value_prop(prop)
                         .and_then(| prop| get_prop_key(prop))
-                        .and_then(| key | {
-                            match key.as_ref() {
-                                "id" | "context" | "values" => Some(true),
-                                _ => None
-                            }
-                        }).is_some()
+                        .is_some_and(| key | self.ctx.options.should_keep_field(key))
                 }).collect();
             }
 
+            if let Some(origin) = self.ctx.create_origin(macro_span) {
+                new_props.push(create_key_value_prop("origin", origin));
+            }
+
+            if let Some(id) = new_props.iter().filter_map(to_key_value_prop).find(|prop| match_prop_key(prop, "id")) {
+                self.ctx.record_message_id(&id.value);
+            }
+
             return Box::new(Expr::Object(ObjectLit {
-                span: DUMMY_SP,
+                span,
                 props: new_props,
             }));
         }
@@ -108,6 +169,17 @@ impl<'a> JsMacroFolder<'a> {
 
 
 impl<'a> Fold for JsMacroFolder<'a> {
+    fn fold_jsx_element(&mut self, el: JSXElement) -> JSXElement {
+        // JSX macros are tokenized with JS macros inside, leave them as is
+        if let JSXElementName::Ident(ident) = &el.opening.name {
+            if self.ctx.is_lingui_ident("Trans", ident) || self.ctx.is_lingui_jsx_choice_cmp(ident) {
+                return el;
+            }
+        }
+
+        el.fold_children_with(self)
+    }
+
     fn fold_expr(&mut self, expr: Expr) -> Expr {
         if let Expr::TaggedTpl(tagged_tpl) = &expr {
             let (is_t, callee) = self.ctx.is_lingui_t_call_expr(&tagged_tpl.tag);
@@ -116,6 +188,8 @@ impl<'a> Fold for JsMacroFolder<'a> {
                 return Expr::Call(self.create_i18n_fn_call_from_tokens(
                     callee,
                     self.ctx.tokenize_tpl(&tagged_tpl.tpl),
+                    tagged_tpl.span,
+                    tagged_tpl.tpl.span,
                 ));
             }
         }
@@ -125,9 +199,11 @@ impl<'a> Fold for JsMacroFolder<'a> {
                  "defineMessage", n
             )) {
                 if call.args.len() == 1 {
-                    let descriptor = self.update_msg_descriptor_props(
-                        call.args.clone().into_iter().next().unwrap().expr
-                    );
+                    let arg = call.args.clone().into_iter().next().unwrap().expr;
+                    self.ctx.move_comments(arg.span(), call.span);
+
+                    let descriptor = self.update_msg_descriptor_props(arg, call.span, call.span);
+                    self.ctx.annotate_message(call.span);
 
                     return *descriptor;
                 }
@@ -143,11 +219,11 @@ impl<'a> Fold for JsMacroFolder<'a> {
             let (is_t, callee) = self.ctx.is_lingui_t_call_expr(callee);
 
             if is_t && expr.args.len() == 1 {
-                let descriptor = self.update_msg_descriptor_props(
-                    expr.args.into_iter().next().unwrap().expr
-                );
+                let arg = expr.args.into_iter().next().unwrap().expr;
+                let arg_span = arg.span();
+                let descriptor = self.update_msg_descriptor_props(arg, arg_span, expr.span);
 
-                return self.create_i18n_fn_call(callee, vec![descriptor.as_arg()]);
+                return self.create_i18n_fn_call(callee, vec![descriptor.as_arg()], expr.span);
             }
         }
 
@@ -156,6 +232,9 @@ impl<'a> Fold for JsMacroFolder<'a> {
             return self.create_i18n_fn_call_from_tokens(
                 None,
                 tokens,
+                expr.span,
+                // choice cases
+                expr.args.get(1).map_or(expr.span, |arg| arg.expr.span()),
             );
         }
 
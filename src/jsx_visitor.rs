@@ -2,8 +2,7 @@ use swc_core::ecma::{
     visit::{Visit, VisitWith},
 };
 use swc_core::ecma::ast::{*};
-use swc_core::common::DUMMY_SP;
-use crate::ast_utils::{get_jsx_attr, get_jsx_attr_value_as_string};
+use crate::ast_utils::{create_boolean_cast, get_jsx_attr, is_boolean_expr, get_jsx_attr_value_as_string};
 use crate::tokens::{IcuChoice, ChoiceCase, CaseOrOffset, MsgToken, TagOpening};
 use regex::{Regex};
 use once_cell::sync::Lazy;
@@ -43,6 +42,17 @@ fn is_allowed_plural_option(key: &str) -> Option<JsWord> {
     None
 }
 
+/// Is a branch of conditional expression contains something to translate
+fn is_translatable_branch(exp: &Expr) -> bool {
+    match exp {
+        Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) | Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_translatable_branch(expr),
+        Expr::Cond(CondExpr { cons, alt, .. }) => is_translatable_branch(cons) || is_translatable_branch(alt),
+        Expr::Bin(BinExpr { op: BinaryOp::LogicalAnd, right, .. }) => is_translatable_branch(right),
+        _ => false
+    }
+}
+
 impl<'a> TransJSXVisitor<'a> {
     // <Plural /> <Select /> <SelectOrdinal />
     fn visit_icu_macro(&mut self, el: &JSXOpeningElement, icu_format: &str) -> Vec<CaseOrOffset> {
@@ -59,43 +69,25 @@ impl<'a> TransJSXVisitor<'a> {
                                 // todo: panic offset might be only a number, other forms are not supported
                             }
                         } else if let Some(key) = is_allowed_plural_option(&ident.sym) {
-                            let mut tokens: Vec<MsgToken> = Vec::new();
-
-                            match attr_value {
+                            let tokens = match attr_value {
                                 // some="# books"
                                 JSXAttrValue::Lit(Lit::Str(str)) => {
-                                    let string: String = str.value.clone().to_string();
-                                    tokens.push(MsgToken::String(string));
+                                    vec![MsgToken::String(str.value.to_string())]
                                 }
-
+                                // some={...}
                                 JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(exp), .. }) => {
-                                    match exp.as_ref() {
-                                        // some={"# books"}
-                                        Expr::Lit(Lit::Str(str)) => {
-                                            tokens.push(MsgToken::String(str.value.clone().to_string()))
-                                        }
-                                        // some={`# books ${name}`}
-                                        Expr::Tpl(tpl) => {
-                                            tokens.extend(self.ctx.tokenize_tpl(tpl));
-                                        }
-                                        // some={`<Books />`}
-                                        Expr::JSXElement(exp) => {
-                                            let mut visitor = TransJSXVisitor::new(&self.ctx);
-                                            exp.visit_children_with(&mut visitor);
-
-                                            tokens.extend(visitor.tokens)
-                                        }
-
-                                        _ => {
-                                            // todo: unsupported syntax
-                                        }
-                                    }
+                                    self.tokenize_choice_case(exp)
                                 }
-
-                                _ => {
-                                    // todo unsupported syntax
+                                // some=<Books />
+                                JSXAttrValue::JSXElement(exp) => {
+                                    self.tokenize_jsx(exp.as_ref())
                                 }
-                            }
+                                // some=<>Books</>
+                                JSXAttrValue::JSXFragment(exp) => {
+                                    self.tokenize_jsx(exp)
+                                }
+                                _ => Vec::new()
+                            };
 
                             choices.push(CaseOrOffset::Case(
                                 ChoiceCase {
@@ -108,7 +100,7 @@ impl<'a> TransJSXVisitor<'a> {
             } else {
                 HANDLER.with(|h| {
                     h.struct_span_warn(el.span, "Unsupported Syntax")
-                        .note("The spread expression could not be analyzed at compile time. Consider to use static values.")
+                        .note("The spread expression could not be analyzed at compile time and is passed to the runtime component as is. Consider to use static values for choice options.")
                         .emit()
                 });
             }
@@ -116,16 +108,92 @@ impl<'a> TransJSXVisitor<'a> {
 
         return choices;
     }
+
+    /// Tokenize a value of the choice case, every form valid in JS choices is valid here as well:
+    /// some={"# books"}, some={`# books ${name}`}, some={<Books />}, some={<>Books</>},
+    /// some={plural(...)}, some={("# books")}
+    /// Anything else is not statically analyzable and used as a placeholder
+    fn tokenize_choice_case(&self, exp: &Expr) -> Vec<MsgToken> {
+        match exp {
+            Expr::JSXElement(el) => self.tokenize_jsx(el.as_ref()),
+            Expr::JSXFragment(fragment) => self.tokenize_jsx(fragment),
+            Expr::Paren(ParenExpr { expr, .. }) => self.tokenize_choice_case(expr),
+            _ => self.ctx.try_tokenize_expr(exp)
+                .unwrap_or_else(|| vec![MsgToken::Expression(Box::new(exp.clone()))]),
+        }
+    }
+
+    /// Lift conditional with translatable branches into ICU select over a boolean value
+    /// {isAdmin ? <b>an admin</b> : "a guest"} -> {0, select, true {<0>an admin</0>} other {a guest}}
+    /// {!isGuest && "an admin"} -> {0, select, true {an admin} other {}}
+    /// `&&` is lifted only when the left operand is a boolean, {count && "items"} renders `0`
+    /// when count is 0, which can't be expressed with select over a boolean
+    fn try_tokenize_conditional(&self, exp: &Expr) -> Option<Vec<MsgToken>> {
+        if !self.ctx.options.lift_jsx_conditionals {
+            return None;
+        }
+
+        let (test, cons, alt) = match exp {
+            Expr::Cond(CondExpr { test, cons, alt, .. }) => (test, cons, Some(alt)),
+            // a && b && "text" is parsed as (a && b) && "text"
+            Expr::Bin(BinExpr { op: BinaryOp::LogicalAnd, left, right, .. }) if is_boolean_expr(left) => (left, right, None),
+            Expr::Paren(ParenExpr { expr, .. }) => return self.try_tokenize_conditional(expr),
+            _ => return None
+        };
+
+        if !is_translatable_branch(cons) && !alt.is_some_and(|alt| is_translatable_branch(alt)) {
+            return None;
+        }
+
+        Some(vec![MsgToken::IcuChoice(IcuChoice {
+            value: create_boolean_cast(test.clone()),
+            format: "select".into(),
+            cases: vec![
+                CaseOrOffset::Case(ChoiceCase {
+                    key: "true".into(),
+                    tokens: self.tokenize_conditional_branch(cons),
+                }),
+                CaseOrOffset::Case(ChoiceCase {
+                    key: "other".into(),
+                    tokens: alt.map(|alt| self.tokenize_conditional_branch(alt)).unwrap_or_default(),
+                }),
+            ],
+        })])
+    }
+
+    fn tokenize_conditional_branch(&self, exp: &Expr) -> Vec<MsgToken> {
+        match exp {
+            // {isAdmin ? "an admin" : null}, rendered as nothing by React
+            Expr::Lit(Lit::Null(_) | Lit::Bool(_)) => Vec::new(),
+            Expr::Ident(ident) if &ident.sym == "undefined" => Vec::new(),
+            _ => self.try_tokenize_conditional(exp)
+                .unwrap_or_else(|| self.tokenize_choice_case(exp))
+        }
+    }
+
+    fn tokenize_jsx<N: VisitWith<Self>>(&self, node: &N) -> Vec<MsgToken> {
+        let mut visitor = TransJSXVisitor::new(self.ctx);
+        node.visit_with(&mut visitor);
+
+        visitor.tokens
+    }
 }
 
 impl<'a> Visit for TransJSXVisitor<'a> {
-    fn visit_jsx_opening_element(&mut self, el: &JSXOpeningElement) {
-        if let JSXElementName::Ident(ident) = &el.name {
-            if self.ctx.is_lingui_ident("Trans", &ident) {
-                el.visit_children_with(self);
+    fn visit_jsx_element(&mut self, el: &JSXElement) {
+        if let JSXElementName::Ident(ident) = &el.opening.name {
+            // nested <Trans> is transparent, only its children are a part of the message
+            if self.ctx.is_lingui_ident("Trans", ident) {
+                el.children.visit_with(self);
                 return;
             }
+        }
 
+        el.visit_children_with(self);
+    }
+
+    fn visit_jsx_opening_element(&mut self, el: &JSXOpeningElement) {
+        if let JSXElementName::Ident(ident) = &el.name {
             if self.ctx.is_lingui_jsx_choice_cmp(&ident) {
                 let value = match get_jsx_attr(&el, "value").and_then(|attr| attr.value.as_ref()) {
                     Some(
@@ -137,7 +205,7 @@ impl<'a> Visit for TransJSXVisitor<'a> {
                     }
                     _ => {
                         Box::new(Expr::Lit(Lit::Null(Null {
-                            span: DUMMY_SP
+                            span: el.span
                         })))
                     }
                 };
@@ -181,6 +249,16 @@ impl<'a> Visit for TransJSXVisitor<'a> {
 
     fn visit_jsx_expr_container(&mut self, cont: &JSXExprContainer) {
         if let JSXExpr::Expr(exp) = &cont.expr {
+            if let Some(tokens) = self.try_tokenize_conditional(exp) {
+                self.tokens.extend(tokens);
+                return;
+            }
+
+            if let Some(text) = self.ctx.try_fold_constant(exp) {
+                self.tokens.push(MsgToken::String(text));
+                return;
+            }
+
             match exp.as_ref() {
                 Expr::Lit(Lit::Str(str)) => {
                     self.tokens.push(
@@ -201,7 +279,11 @@ impl<'a> Visit for TransJSXVisitor<'a> {
                 }
 
                 Expr::JSXElement(jsx) => {
-                    jsx.visit_children_with(self);
+                    jsx.visit_with(self);
+                }
+
+                Expr::JSXFragment(fragment) => {
+                    fragment.visit_with(self);
                 }
 
                 Expr::Tpl(tpl) => {
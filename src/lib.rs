@@ -1,17 +1,18 @@
-use std::collections::HashSet;
 use swc_core::{
+    common::{comments::{Comment, CommentKind, Comments}, errors::SourceMapper, BytePos, Loc, Mark, Span, Spanned, SyntaxContext, DUMMY_SP},
     ecma::{
-        utils::quote_ident,
         ast::*,
         visit::{Fold, FoldWith, VisitWith},
     },
     plugin::{
+        errors::HANDLER,
         metadata::TransformPluginMetadataContextKind,
         plugin_transform,
         proxies::TransformPluginProgramMetadata,
     },
 };
 
+#[cfg(test)]
 mod tests;
 mod normalize_witespaces_jsx;
 mod normalize_witespaces_js;
@@ -22,6 +23,9 @@ mod macro_utils;
 mod jsx_visitor;
 mod js_macro_folder;
 mod options;
+mod pseudo;
+mod debug_markers;
+mod icu;
 
 use builder::*;
 use ast_utils::*;
@@ -35,6 +39,8 @@ use crate::options::{*};
 pub struct LinguiMacroFolder {
     has_lingui_macro_imports: bool,
     ctx: MacroCtx,
+    /// span of the innermost statement, leading comments of JSX macros are moved there
+    stmt_span: Span,
 }
 
 impl LinguiMacroFolder {
@@ -42,9 +48,23 @@ impl LinguiMacroFolder {
         LinguiMacroFolder {
             has_lingui_macro_imports: false,
             ctx: MacroCtx::new(options),
+            stmt_span: DUMMY_SP,
         }
     }
 
+    /// Comments of the replaced nodes are moved to their replacements
+    pub fn with_comments<C: Comments + 'static>(mut self, comments: C) -> LinguiMacroFolder {
+        self.ctx.comments = Some(Box::new(comments));
+        self
+    }
+
+    /// Add origin metadata of messages, `filename` is relative to cwd
+    pub fn with_source_map<F: Fn(BytePos) -> Loc + 'static>(mut self, filename: String, lookup_char_pos: F) -> LinguiMacroFolder {
+        self.ctx.filename = Some(filename);
+        self.ctx.lookup_char_pos = Some(Box::new(lookup_char_pos));
+        self
+    }
+
     // <Trans>Message</Trans>
     // <Plural />
     fn transform_jsx_macro(&mut self, el: JSXElement, is_trans_el: bool) -> JSXElement {
@@ -56,20 +76,42 @@ impl LinguiMacroFolder {
             el.visit_children_with(&mut trans_visitor);
         }
 
-        let parsed = MessageBuilder::parse(trans_visitor.tokens, true);
+        // generated nodes are mapped to the tag name, not to the start of the element,
+        // so leading comments of the element aren't printed inside the tag
+        let span = el.opening.name.span();
+        let parsed = MessageBuilder::parse(trans_visitor.tokens, true, span, &self.ctx.options);
         let id_attr = get_jsx_attr(&el.opening, "id");
-
-        let mut attrs = vec![
-            create_jsx_attribute(
-                if let Some(_) = id_attr { "message" } else { "id" }.into(),
-                parsed.message,
-            ),
-        ];
+        let explicit_id = id_attr
+            .and_then(|attr| attr.value.as_ref())
+            .and_then(get_jsx_attr_value_as_string);
+
+        let mut attrs = match (self.ctx.generated_id(&parsed.message), explicit_id) {
+            (Some(id), _) if id_attr.is_none() => {
+                let message = self.ctx.compile_message(self.ctx.mark_message(parsed.display_message.unwrap_or(parsed.message), &id));
+
+                vec![
+                    create_jsx_attribute("id", id, span),
+                    create_jsx_attribute("message", message, span),
+                ]
+            }
+            (_, Some(id)) => vec![
+                create_jsx_attribute("message", self.ctx.compile_message(
+                    self.ctx.mark_message(parsed.display_message.unwrap_or(parsed.message), &Expr::Lit(Lit::Str(id[..].into())))
+                ), span),
+            ],
+            (_, None) if id_attr.is_some() => vec![
+                create_jsx_attribute("message", self.ctx.compile_message(parsed.display_message.unwrap_or(parsed.message)), span),
+            ],
+            _ => vec![
+                create_jsx_attribute("id", parsed.message, span),
+            ],
+        };
 
         if let Some(exp) = parsed.values {
             attrs.push(create_jsx_attribute(
                 "values",
                 exp,
+                span,
             ));
         }
 
@@ -77,20 +119,44 @@ impl LinguiMacroFolder {
             attrs.push(create_jsx_attribute(
                 "components",
                 exp,
+                span,
             ));
         }
 
-        attrs.extend(
-            pick_jsx_attrs(el.opening.attrs, HashSet::from(["id", "render", "comment", "context", "i18n"]))
-        );
+        let options = &self.ctx.options;
+
+        let mut picked = pick_jsx_attrs(el.opening.attrs, |name| {
+            matches!(name, "id" | "render" | "comment" | "context" | "i18n")
+                || options.is_jsx_passthrough_attr(name)
+        });
+
+        // generated attributes go after the last spread, so they can't be overridden by it
+        let after_spreads = picked.iter()
+            .rposition(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)))
+            .map_or(0, |index| index + 1);
+
+        let rest = picked.split_off(after_spreads);
+        attrs.splice(0..0, picked);
+        attrs.extend(rest);
 
-        if self.ctx.options.strip_non_essential_fields {
-            attrs = pick_jsx_attrs(attrs, HashSet::from(["id", "render", "i18n", "context", "values", "components"]))
+        attrs = pick_jsx_attrs(attrs, |name| {
+            options.should_keep_field(name) || options.is_jsx_passthrough_attr(name)
+        });
+
+        if let Some(origin) = self.ctx.create_origin(el.span) {
+            attrs.push(create_jsx_attribute("origin", origin, span));
         }
 
         self.ctx.should_add_trans_import = true;
 
-        let (_, trans_export) = self.ctx.options.runtime_modules.trans.clone();
+        if let Some(id) = attrs.iter().find_map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), value: Some(value), .. }) if &ident.sym == "id" => {
+                get_jsx_attr_value_as_string(value)
+            }
+            _ => None,
+        }) {
+            self.ctx.record_message_id(&Expr::Lit(Lit::Str(id[..].into())));
+        }
 
         return JSXElement {
             span: el.span,
@@ -99,9 +165,7 @@ impl LinguiMacroFolder {
             opening: JSXOpeningElement {
                 self_closing: true,
                 span: el.opening.span,
-                name: JSXElementName::Ident(
-                    Ident::new(trans_export.into(), el.opening.span)
-                ),
+                name: self.ctx.runtime_trans_name(el.opening.name.span()),
                 type_args: None,
                 attrs,
             },
@@ -111,38 +175,64 @@ impl LinguiMacroFolder {
 
 impl<'a> Fold for LinguiMacroFolder {
     fn fold_module_items(&mut self, mut n: Vec<ModuleItem>) -> Vec<ModuleItem> {
-        let mut has_i18n_import = false;
-        let mut has_trans_import = false;
-
-        let (i18n_source, i18n_export) = self.ctx.options.runtime_modules.i18n.clone();
-        let (trans_source, trans_export) = self.ctx.options.runtime_modules.trans.clone();
+        // local identifiers of already imported runtime
+        let mut i18n_import: Option<Id> = None;
+        let mut trans_import: Option<Id> = None;
+        // existing declarations of runtime modules, missing specifiers are added there
+        let mut i18n_import_index: Option<usize> = None;
+        let mut trans_import_index: Option<usize> = None;
+
+        // "use client" or "use server" directive selects runtime modules
+        self.ctx.runtime_modules = n.iter()
+            .map_while(get_directive)
+            .find_map(|directive| self.ctx.options.directive_runtime_modules.get(&directive[..]))
+            .unwrap_or(&self.ctx.options.runtime_modules)
+            .clone();
+
+        // "use server" modules may export only async functions
+        let is_server_module = n.iter()
+            .map_while(get_directive)
+            .any(|directive| directive == "use server");
+
+        let (i18n_source, i18n_export) = self.ctx.runtime_modules.i18n.clone();
+        let (trans_source, trans_export) = self.ctx.runtime_modules.trans.clone();
 
         let mut insert_index: usize = 0;
         let mut index = 0;
+        let mut macro_import_spans: Vec<Span> = Vec::new();
 
         n.retain(|m| {
+            match m {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(decl)))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(decl), .. })) => {
+                    self.ctx.register_const_decl(decl);
+                }
+                _ => {}
+            }
+
             if let ModuleItem::ModuleDecl(ModuleDecl::Import(imp)) = m {
                 // drop macro imports
                 if &imp.src.value == "@lingui/macro" {
                     self.has_lingui_macro_imports = true;
                     self.ctx.register_macro_import(imp);
+                    macro_import_spans.push(imp.span);
                     insert_index = index;
                     return false;
                 }
 
-                if &imp.src.value == &i18n_source && !imp.type_only {
-                    for spec in &imp.specifiers {
-                        if let ImportSpecifier::Named(spec) = spec {
-                            has_i18n_import = if !has_i18n_import { &spec.local.sym == &i18n_export } else { true };
-                        }
+                if imp.src.value == i18n_source {
+                    i18n_import = i18n_import.take().or_else(|| find_import_local(imp, &i18n_export));
+
+                    if i18n_import_index.is_none() && is_extendable_import(imp, &i18n_export) {
+                        i18n_import_index = Some(index);
                     }
                 }
 
-                if &imp.src.value == &trans_source && !imp.type_only {
-                    for spec in &imp.specifiers {
-                        if let ImportSpecifier::Named(spec) = spec {
-                            has_trans_import = if !has_trans_import { &spec.local.sym == &trans_export } else { true };
-                        }
+                if imp.src.value == trans_source {
+                    trans_import = trans_import.take().or_else(|| find_import_local(imp, &trans_export));
+
+                    if trans_import_index.is_none() && is_extendable_import(imp, &trans_export) {
+                        trans_import_index = Some(index);
                     }
                 }
             }
@@ -151,14 +241,78 @@ impl<'a> Fold for LinguiMacroFolder {
           true
         });
 
+      // generated references use a private mark, so local bindings with the same name
+      // can't shadow the injected imports, hygiene renames them instead
+      let runtime_ctxt = SyntaxContext::empty().apply_mark(Mark::new());
+      let has_i18n_import = i18n_import.is_some();
+      let has_trans_import = trans_import.is_some();
+
+      self.ctx.runtime_i18n = i18n_import.unwrap_or_else(|| (i18n_export.local_name("i18n").into(), runtime_ctxt));
+      self.ctx.runtime_trans = trans_import.unwrap_or_else(|| (trans_export.local_name("Trans").into(), runtime_ctxt));
+
       n = n.fold_children_with(self);
 
+      // runtime imports replace the macro import
+      let import_span = macro_import_spans.last().copied().unwrap_or(DUMMY_SP);
+
       if !has_i18n_import && self.ctx.should_add_18n_import {
-        n.insert(insert_index, create_import(i18n_source.into(), quote_ident!(i18n_export[..])));
+        let specifier = create_import_specifier(self.ctx.runtime_i18n_ident(DUMMY_SP), &i18n_export);
+
+        if let Some(index) = i18n_import_index {
+          add_import_specifier(&mut n[index], specifier);
+        } else {
+          n.insert(insert_index, create_import(i18n_source.into(), specifier, import_span));
+          trans_import_index = trans_import_index.map(|index| if index >= insert_index { index + 1 } else { index });
+        }
       }
 
       if !has_trans_import && self.ctx.should_add_trans_import {
-        n.insert(insert_index, create_import(trans_source.into(), quote_ident!(trans_export[..])));
+        let specifier = create_import_specifier(self.ctx.runtime_trans_ident(DUMMY_SP), &trans_export);
+
+        if let Some(index) = trans_import_index {
+          add_import_specifier(&mut n[index], specifier);
+        } else {
+          n.insert(insert_index, create_import(trans_source.into(), specifier, import_span));
+        }
+      }
+
+      self.ctx.remove_ambiguous_pure_annotations(&n);
+
+      if let Some(target) = n.get(insert_index).map(|item| item.span()) {
+        for span in macro_import_spans {
+          self.ctx.move_comments(span, target);
+        }
+      }
+
+      if let Some(ids) = self.ctx.message_ids_json() {
+        // the export is replaced with the comment where it's not allowed
+        let manifest = match self.ctx.options.message_manifest {
+          Some(MessageManifest::Export) if is_server_module => Some(MessageManifest::Comment),
+          manifest => manifest,
+        };
+
+        match manifest {
+          Some(MessageManifest::Export) => {
+            let elems = self.ctx.message_ids.iter()
+              .map(|id| Some(ExprOrSpread { spread: None, expr: Box::new(Expr::Lit(Lit::Str(id[..].into()))) }))
+              .collect();
+
+            n.push(create_const_export("__linguiMessageIds", Box::new(Expr::Array(ArrayLit { span: DUMMY_SP, elems }))));
+          }
+          Some(MessageManifest::Comment) => {
+            let target = n.iter().map(|item| item.span()).find(|span| !span.is_dummy());
+
+            if let (Some(comments), Some(target)) = (&self.ctx.comments, target) {
+              comments.add_leading(target.lo, Comment {
+                kind: CommentKind::Block,
+                span: DUMMY_SP,
+                // "*/" in IDs would close the comment
+                text: format!("@lingui-message-ids {}", ids.replace("*/", "*\\/")).into(),
+              });
+            }
+          }
+          None => {}
+        }
       }
 
       n
@@ -192,6 +346,22 @@ impl<'a> Fold for LinguiMacroFolder {
             .fold_children_with(self)
     }
 
+    fn fold_module_item(&mut self, item: ModuleItem) -> ModuleItem {
+        let parent_span = std::mem::replace(&mut self.stmt_span, item.span());
+        let item = item.fold_children_with(self);
+        self.stmt_span = parent_span;
+
+        item
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        let parent_span = std::mem::replace(&mut self.stmt_span, stmt.span());
+        let stmt = stmt.fold_children_with(self);
+        self.stmt_span = parent_span;
+
+        stmt
+    }
+
     fn fold_jsx_element(&mut self, mut el: JSXElement) -> JSXElement {
         // If no package that we care about is imported, skip the following
         // transformation logic.
@@ -199,39 +369,101 @@ impl<'a> Fold for LinguiMacroFolder {
             return el;
         }
 
-        // apply JS Macro transformations to jsx elements
-        // before they will be extracted as message components
-        el = el.fold_with(&mut JsMacroFolder::new(&mut self.ctx));
-
         if let JSXElementName::Ident(ident) = &el.opening.name {
-            if self.ctx.is_lingui_ident("Trans", &ident) {
-                return self.transform_jsx_macro(el, true);
-            }
+            let is_trans_el = self.ctx.is_lingui_ident("Trans", ident);
+
+            if is_trans_el || self.ctx.is_lingui_jsx_choice_cmp(ident) {
+                // JSX elements don't print their leading comments, so lint suppressions
+                // before the macro are moved to the start of the statement
+                self.ctx.move_leading_comments(el.span, self.stmt_span);
 
-            if self.ctx.is_lingui_jsx_choice_cmp(&ident) {
-                return self.transform_jsx_macro(el, false);
+                // JSX macros are tokenized before JS macros inside them are transformed,
+                // so choice calls such as {plural(...)} become a part of the message.
+                // JS macros left in values and components are transformed afterwards
+                return self.transform_jsx_macro(el, is_trans_el).fold_children_with(self);
             }
         }
 
+        // apply JS Macro transformations to jsx elements
+        // before they will be extracted as message components
+        el = el.fold_with(&mut JsMacroFolder::new(&mut self.ctx));
+
         el.fold_children_with(self)
     }
 }
 
 
+fn report_config_error(e: ConfigError) {
+    HANDLER.with(|h| {
+        let mut diagnostic = h.struct_err(&e.message);
+
+        if let Some(note) = &e.note {
+            diagnostic.note(note);
+        }
+
+        diagnostic.emit();
+    });
+}
+
+/// Transform the program with the plugin config as passed by SWC, `path` is relative to the cwd.
+/// Invalid config is reported and the program is returned as is, as well as files not matched by globs.
+/// `setup` attaches comments and source map of the host to the folder
+fn transform_program<F>(program: Program, plugin_config: &str, cwd: &str, path: Option<String>, env_name: &str, setup: F) -> Program
+    where F: FnOnce(LinguiMacroFolder, Option<String>) -> LinguiMacroFolder
+{
+    let config = LinguiJsOptions::parse(plugin_config)
+        .and_then(|config| config.with_lingui_config(cwd));
+
+    // invalid config is reported, the file is left untouched
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            report_config_error(e);
+            return program;
+        }
+    };
+
+    // file is not included or excluded by globs
+    let Some(config) = config.for_env(env_name).for_file(path.as_deref()) else {
+        return program;
+    };
+
+    let mut folder = setup(LinguiMacroFolder::new(config.to_options(env_name)), path);
+
+    program.fold_with(&mut folder)
+}
+
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    let config = serde_json::from_str::<LinguiJsOptions>(
-        &metadata
-            .get_transform_plugin_config()
-            .expect("failed to get plugin config for lingui-plugin"),
-    )
-        .expect("invalid config for lingui-plugin");
-
-    let config = config.to_options(
-        &metadata
-            .get_context(&TransformPluginMetadataContextKind::Env)
-            .unwrap_or_default(),
-    );
-
-    program.fold_with(&mut LinguiMacroFolder::new(config))
+    let cwd = metadata
+        .get_context(&TransformPluginMetadataContextKind::Cwd)
+        .unwrap_or_default();
+
+    let plugin_config = metadata
+        .get_transform_plugin_config()
+        .unwrap_or_else(|| "{}".into());
+
+    let path = metadata
+        .get_context(&TransformPluginMetadataContextKind::Filename)
+        .map(|filename| relative_path(
+            &filename,
+            &cwd,
+        ));
+
+    let env_name = metadata
+        .get_context(&TransformPluginMetadataContextKind::Env)
+        .unwrap_or_default();
+
+    transform_program(program, &plugin_config, &cwd, path, &env_name, |mut folder, path| {
+        if let Some(comments) = metadata.comments {
+            folder = folder.with_comments(comments);
+        }
+
+        if let Some(path) = path {
+            let source_map = metadata.source_map;
+            folder = folder.with_source_map(path, move |pos| source_map.lookup_char_pos(pos));
+        }
+
+        folder
+    })
 }
@@ -1,36 +1,325 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use crate::ast_utils::*;
 use crate::tokens::*;
+use crate::debug_markers;
+use crate::icu;
 use swc_core::{
+    common::{comments::{Comment, CommentKind, Comments}, BytePos, Loc, Span, DUMMY_SP},
     ecma::{
         ast::*,
-        atoms::JsWord
+        atoms::JsWord,
+        visit::{Visit, VisitWith},
     },
 };
-use crate::LinguiOptions;
+use crate::{LinguiOptions, RuntimeExport, RuntimeModulesConfigMapNormalized};
 
 const LINGUI_T: &str = &"t";
 
+/// Format number the same way as JS does, for numbers where Rust and JS formatting are equal.
+/// JS uses exponential notation below 1e-6 and from 1e21, these numbers are not formatted
+fn format_js_number(value: f64) -> Option<String> {
+    if value == 0.0 {
+        // -0 is "0" in JS
+        Some("0".into())
+    } else if value.is_finite() && value.abs() >= 1e-6 && value.abs() < 1e21 {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
 #[derive(Default)]
 pub struct MacroCtx {
     // export name -> local name
     imports_id_map: HashMap<JsWord, Id>,
     // local name -> export name
     imports_id_map_inverted: HashMap<Id, JsWord>,
+    // module level const name -> folded literal value
+    constants: HashMap<Id, String>,
+
+    // runtime modules selected for the current module by its directive
+    pub runtime_modules: RuntimeModulesConfigMapNormalized,
+    // local names of the runtime i18n instance and Trans component
+    pub runtime_i18n: Id,
+    pub runtime_trans: Id,
 
     pub should_add_18n_import: bool,
     pub should_add_trans_import: bool,
     pub options: LinguiOptions,
+    pub comments: Option<Box<dyn Comments>>,
+    // path of the file relative to cwd and position lookup for origin metadata
+    pub filename: Option<String>,
+    pub lookup_char_pos: Option<Box<dyn Fn(BytePos) -> Loc>>,
+    // IDs of messages generated in the module, sorted for the manifest
+    pub message_ids: BTreeSet<String>,
+    // spans of runtime calls annotated as pure
+    pure_spans: Vec<Span>,
+}
+
+/// Collect spans of all calls in the module
+#[derive(Default)]
+struct CallSpansVisitor {
+    spans: Vec<Span>,
+}
+
+impl Visit for CallSpansVisitor {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        self.spans.push(call.span);
+        call.visit_children_with(self);
+    }
+
+    fn visit_opt_call(&mut self, call: &OptCall) {
+        self.spans.push(call.span);
+        call.visit_children_with(self);
+    }
+
+    fn visit_tagged_tpl(&mut self, tpl: &TaggedTpl) {
+        self.spans.push(tpl.span);
+        tpl.visit_children_with(self);
+    }
 }
 
 impl MacroCtx {
     pub fn new(options: LinguiOptions) -> MacroCtx {
         MacroCtx {
+            runtime_modules: options.runtime_modules.clone(),
             options,
             ..Default::default()
         }
     }
 
+    /// Move comments of the replaced node to its replacement,
+    /// so lint suppressions and bundler magic comments survive the transform
+    pub fn move_comments(&self, from: Span, to: Span) {
+        if let Some(comments) = &self.comments {
+            if from.is_dummy() || to.is_dummy() {
+                return;
+            }
+
+            if from.lo != to.lo {
+                comments.move_leading(from.lo, to.lo);
+            }
+
+            if from.hi != to.hi {
+                comments.move_trailing(from.hi, to.hi);
+            }
+        }
+    }
+
+    /// Move only leading comments, e.g. to the start of the enclosing node
+    pub fn move_leading_comments(&self, from: Span, to: Span) {
+        if let Some(comments) = &self.comments {
+            if !from.is_dummy() && !to.is_dummy() && from.lo != to.lo {
+                comments.move_leading(from.lo, to.lo);
+            }
+        }
+    }
+
+    /// Mark generated message with /*i18n*/ comment, so extractor could find it in a compiled code
+    pub fn annotate_message(&self, span: Span) {
+        if !self.options.annotate_messages || span.is_dummy() {
+            return;
+        }
+
+        if let Some(comments) = &self.comments {
+            let is_annotated = comments.get_leading(span.lo)
+                .is_some_and(|comments| comments.iter().any(|c| c.text == "i18n"));
+
+            if !is_annotated {
+                comments.add_leading(span.lo, Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: "i18n".into(),
+                });
+            }
+        }
+    }
+
+    /// Mark generated runtime call with /*#__PURE__*/ comment, so minifiers could drop it when unused
+    pub fn annotate_pure(&mut self, span: Span) {
+        if !self.options.pure_annotations || span.is_dummy() {
+            return;
+        }
+
+        if let Some(comments) = &self.comments {
+            comments.add_pure_comment(span.lo);
+            self.pure_spans.push(span);
+        }
+    }
+
+    /// Drop pure annotations of calls which share the start position with an enclosing call.
+    /// `t`Hello`.split("").forEach(...)` starts at the same position as the macro,
+    /// so the annotation would mark the whole chain with its side effects as pure
+    pub fn remove_ambiguous_pure_annotations(&self, items: &[ModuleItem]) {
+        let comments = match &self.comments {
+            Some(comments) if !self.pure_spans.is_empty() => comments,
+            _ => return,
+        };
+
+        let mut visitor = CallSpansVisitor::default();
+        items.iter().for_each(|item| item.visit_with(&mut visitor));
+
+        for span in &self.pure_spans {
+            if !visitor.spans.iter().any(|call| call.lo == span.lo && call.hi != span.hi) {
+                continue;
+            }
+
+            if let Some(leading) = comments.take_leading(span.lo) {
+                comments.add_leading_comments(span.lo, leading.into_iter().filter(|c| c.text != "#__PURE__").collect());
+            }
+        }
+    }
+
+    /// Local binding of the runtime i18n instance, resolves to the injected or existing import
+    pub fn runtime_i18n_ident(&self, span: Span) -> Ident {
+        Ident::new(self.runtime_i18n.0.clone(), span.with_ctxt(self.runtime_i18n.1))
+    }
+
+    /// Local binding of the runtime Trans component, resolves to the injected or existing import
+    pub fn runtime_trans_ident(&self, span: Span) -> Ident {
+        Ident::new(self.runtime_trans.0.clone(), span.with_ctxt(self.runtime_trans.1))
+    }
+
+    /// Reference to the runtime i18n instance, `i18n` or `i18nModule.i18n` for namespace imports,
+    /// called as `getI18n()` if the export is a getter
+    pub fn runtime_i18n_expr(&self, span: Span) -> Box<Expr> {
+        let ident = self.runtime_i18n_ident(span);
+
+        let i18n = match &self.runtime_modules.i18n.1 {
+            RuntimeExport::Namespace(member) => Box::new(Expr::Member(MemberExpr {
+                span,
+                obj: Box::new(ident.into()),
+                prop: MemberProp::Ident(Ident::new(member[..].into(), span)),
+            })),
+            _ => Box::new(ident.into()),
+        };
+
+        if !self.runtime_modules.i18n_getter {
+            return i18n;
+        }
+
+        Box::new(Expr::Call(CallExpr {
+            span,
+            callee: Callee::Expr(i18n),
+            args: vec![],
+            type_args: None,
+        }))
+    }
+
+    /// Name of the runtime Trans component, `<Trans>` or `<TransModule.Trans>` for namespace imports
+    pub fn runtime_trans_name(&self, span: Span) -> JSXElementName {
+        let ident = self.runtime_trans_ident(span);
+
+        match &self.runtime_modules.trans.1 {
+            RuntimeExport::Namespace(member) => JSXElementName::JSXMemberExpr(JSXMemberExpr {
+                obj: JSXObject::Ident(ident),
+                prop: Ident::new(member[..].into(), span),
+            }),
+            _ => JSXElementName::Ident(ident),
+        }
+    }
+
+    /// Origin of the message in the source code, ["src/App.js", line, column]
+    /// Return None if origin is disabled or there is no filename and source map
+    pub fn create_origin(&self, span: Span) -> Option<Box<Expr>> {
+        if !self.options.add_origin || span.is_dummy() {
+            return None;
+        }
+
+        let filename = self.filename.as_ref()?;
+        let loc = self.lookup_char_pos.as_ref()?(span.lo);
+
+        let elems = vec![
+            Expr::Lit(Lit::Str(filename[..].into())),
+            Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value: loc.line as f64, raw: None })),
+            Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value: loc.col.0 as f64, raw: None })),
+        ];
+
+        Some(Box::new(Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: elems.into_iter().map(|exp| Some(ExprOrSpread { spread: None, expr: Box::new(exp) })).collect(),
+        })))
+    }
+
+    /// ID for the message without explicit ID, prefixed with the namespace
+    /// or the message text if the message is pseudo localized, isolated, marked or compiled.
+    /// Return None if the message is used as ID as is
+    pub fn generated_id(&self, message: &Expr) -> Option<Box<Expr>> {
+        if let Expr::Lit(Lit::Str(Str { value, span, .. })) = message {
+            let id = match &self.options.id_namespace {
+                Some(namespace) => format!("{namespace}.{value}"),
+                None if self.options.pseudo_localization.is_some()
+                    || self.options.bidi_isolation.is_some()
+                    || self.options.debug_markers.is_some()
+                    || self.options.compile_messages => value.to_string(),
+                None => return None,
+            };
+
+            return Some(Box::new(Expr::Lit(Lit::Str(Str {
+                span: *span,
+                value: id.into(),
+                raw: None,
+            }))));
+        }
+
+        None
+    }
+
+    /// Prepend debug markers carrying the ID to the message, if enabled
+    pub fn mark_message(&self, message: Box<Expr>, id: &Expr) -> Box<Expr> {
+        let markers = match self.options.debug_markers {
+            Some(markers) => markers,
+            None => return message,
+        };
+
+        match (*message, id) {
+            (Expr::Lit(Lit::Str(Str { value, span, .. })), Expr::Lit(Lit::Str(id))) => {
+                Box::new(Expr::Lit(Lit::Str(Str {
+                    span,
+                    value: debug_markers::mark(&value, &id.value, markers).into(),
+                    raw: None,
+                })))
+            }
+            (message, _) => Box::new(message),
+        }
+    }
+
+    /// Message in Lingui's compiled format, so it's not parsed at runtime.
+    /// Invalid messages are left as is, the runtime reports them
+    pub fn compile_message(&self, message: Box<Expr>) -> Box<Expr> {
+        if !self.options.compile_messages {
+            return message;
+        }
+
+        match message.as_ref() {
+            Expr::Lit(Lit::Str(Str { value, span, .. })) => match icu::compile_message(value) {
+                Ok(compiled) => Box::new(compiled.into_expr(*span)),
+                Err(_) => message,
+            },
+            _ => message,
+        }
+    }
+
+    /// Record ID of the generated message for the manifest, IDs which are not string literals are skipped
+    pub fn record_message_id(&mut self, id: &Expr) {
+        if self.options.message_manifest.is_none() {
+            return;
+        }
+
+        if let Expr::Lit(Lit::Str(Str { value, .. })) = id {
+            self.message_ids.insert(value.to_string());
+        }
+    }
+
+    /// Recorded IDs as JSON array, None if there are no messages in the module
+    pub fn message_ids_json(&self) -> Option<String> {
+        if self.message_ids.is_empty() {
+            return None;
+        }
+
+        serde_json::to_string(&self.message_ids).ok()
+    }
+
     /// is given ident exported from @lingui/macro? and one of choice functions?
     fn is_lingui_fn_choice_cmp(&self, ident: &Ident) -> bool {
         self.is_lingui_ident("plural", ident) ||
@@ -100,6 +389,67 @@ impl MacroCtx {
         }
     }
 
+    /// const GREETING = "Hello";
+    /// Remember module level constants initialised with literals, so they could be folded into messages
+    pub fn register_const_decl(&mut self, decl: &VarDecl) {
+        if !self.options.fold_constants || decl.kind != VarDeclKind::Const {
+            return;
+        }
+
+        for declarator in &decl.decls {
+            if let (Pat::Ident(BindingIdent { id, .. }), Some(init)) = (&declarator.name, &declarator.init) {
+                if let Some(text) = self.try_fold_constant(init) {
+                    self.constants.insert(id.to_id(), text);
+                }
+            }
+        }
+    }
+
+    /// Evaluate expression which is known at compile time as a message text
+    /// "2.0", 3, `v${"2.0"}` or a reference to a registered constant
+    /// Return None if folding is disabled or expression is not a constant
+    pub fn try_fold_constant(&self, expr: &Expr) -> Option<String> {
+        if !self.options.fold_constants {
+            return None;
+        }
+
+        let text = match expr {
+            Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+            Expr::Lit(Lit::Num(Number { value, .. })) => format_js_number(*value)?,
+            Expr::Unary(UnaryExpr { op: UnaryOp::Minus, arg, .. }) => {
+                if let Expr::Lit(Lit::Num(Number { value, .. })) = arg.as_ref() {
+                    format_js_number(-value)?
+                } else {
+                    return None;
+                }
+            }
+            Expr::Tpl(tpl) => {
+                let mut text = String::new();
+
+                for (i, tpl_element) in tpl.quasis.iter().enumerate() {
+                    text.push_str(&tpl_element.raw);
+
+                    if let Some(exp) = tpl.exprs.get(i) {
+                        text.push_str(&self.try_fold_constant(exp)?);
+                    }
+                }
+
+                text
+            }
+            Expr::Paren(ParenExpr { expr, .. }) => self.try_fold_constant(expr)?,
+            Expr::Ident(ident) => self.constants.get(&ident.to_id())?.clone(),
+            _ => return None
+        };
+
+        // folded text should not change the message syntax, apostrophe escapes placeholders,
+        // `#` is a count in plural cases and `<0>` is a component in `Trans`
+        if text.contains(['{', '}', '\'', '#', '<']) {
+            return None;
+        }
+
+        Some(text)
+    }
+
     /// Receive TemplateLiteral with variables and return MsgTokens
     pub fn tokenize_tpl(&self, tpl: &Tpl) -> Vec<MsgToken> {
         let mut tokens: Vec<MsgToken> = Vec::with_capacity(tpl.quasis.len());
@@ -108,6 +458,11 @@ impl MacroCtx {
             tokens.push(MsgToken::String(tpl_element.raw.to_string()));
 
             if let Some(exp) = tpl.exprs.get(i) {
+                if let Some(text) = self.try_fold_constant(exp) {
+                    tokens.push(MsgToken::String(text));
+                    continue;
+                }
+
                 if let Expr::Call(call) = exp.as_ref() {
                     if let Some(call_tokens) = self.try_tokenize_call_expr_as_choice_cmp(call) {
                         tokens.extend(call_tokens);
@@ -155,8 +510,8 @@ impl MacroCtx {
         return None;
     }
 
-    pub fn try_tokenize_expr(&self, expr: &Box<Expr>) -> Option<Vec<MsgToken>> {
-        match expr.as_ref() {
+    pub fn try_tokenize_expr(&self, expr: &Expr) -> Option<Vec<MsgToken>> {
+        match expr {
             // String Literal: "has # friend"
             Expr::Lit(Lit::Str(str)) => {
                 Some(vec!(MsgToken::String(str.clone().value.to_string())))
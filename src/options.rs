@@ -1,82 +1,725 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use once_cell::sync::Lazy;
 use serde::Deserialize;
+use serde::de::IgnoredAny;
 
-#[derive(Deserialize, Debug, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct LinguiJsOptions {
+    lingui_config: Option<LinguiConfigPath>,
+    env: Option<HashMap<String, LinguiJsOptions>>,
+    strip_non_essential_fields: Option<StripNonEssentialFields>,
+    keep_fields: Option<Vec<String>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    overrides: Option<Vec<LinguiJsOverride>>,
     runtime_modules: Option<RuntimeModulesConfigMap>,
+    directive_runtime_modules: Option<HashMap<String, RuntimeModulesConfigMap>>,
+    jsx_passthrough_attrs: Option<Vec<String>>,
+    lift_jsx_conditionals: Option<bool>,
+    fold_constants: Option<bool>,
+    annotate_messages: Option<bool>,
+    pure_annotations: Option<bool>,
+    id_namespace: Option<String>,
+    id_namespace_pattern: Option<String>,
+    add_origin: Option<bool>,
+    pseudo_localization: Option<PseudoLocalizationConfig>,
+    debug_markers: Option<DebugMarkers>,
+    bidi_isolation: Option<BidiIsolationConfig>,
+    normalize_unicode: Option<bool>,
+    message_manifest: Option<MessageManifest>,
+    compile_messages: Option<bool>,
+}
+
+/// `true`, `false` or `"auto"` to strip in all envs except `development`
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(untagged, expecting = "expected true, false or \"auto\"")]
+enum StripNonEssentialFields {
+    Bool(bool),
+    Mode(StripMode),
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum StripMode {
+    Auto,
+}
+
+/// `true` for defaults or `{ "expansion": 1.3, "markers": ["[", "]"] }`
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(untagged, expecting = "expected a boolean or an object with expansion and markers")]
+enum PseudoLocalizationConfig {
+    Enabled(bool),
+    Options(PseudoLocalizationOptions),
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+struct PseudoLocalizationOptions {
+    expansion: Option<f64>,
+    markers: Option<(String, String)>,
+}
+
+/// `true` or `{ "exclude": ["count"] }` with placeholders left bare
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(untagged, expecting = "expected a boolean or an object with exclude")]
+enum BidiIsolationConfig {
+    Enabled(bool),
+    Options(BidiIsolationOptions),
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+struct BidiIsolationOptions {
+    exclude: Option<Vec<String>>,
+}
+
+/// Fields required by the runtime, never stripped
+const ESSENTIAL_FIELDS: [&str; 6] = ["id", "context", "values", "components", "render", "i18n"];
+
+/// `true` to load `lingui.config.json` or a path relative to the cwd
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged, expecting = "expected a boolean or a path relative to the cwd")]
+enum LinguiConfigPath {
+    Enabled(bool),
+    Path(String),
+}
+
+/// Fields of the project's `lingui.config.json`, only `runtimeConfigModule` affects the transform.
+/// Fields for extraction and compilation of catalogs are accepted and ignored, unknown fields are rejected
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[allow(dead_code)] // ignored fields are only deserialized
+struct LinguiConfig {
+    runtime_config_module: Option<LinguiRuntimeConfigModule>,
+    /// custom macro packages are not supported, macros are imported from `@lingui/macro` only
+    r#macro: Option<IgnoredAny>,
+
+    #[serde(rename = "$schema")]
+    schema: Option<IgnoredAny>,
+    locales: Option<IgnoredAny>,
+    source_locale: Option<IgnoredAny>,
+    fallback_locales: Option<IgnoredAny>,
+    pseudo_locale: Option<IgnoredAny>,
+    catalogs: Option<IgnoredAny>,
+    catalogs_merge_path: Option<IgnoredAny>,
+    compile_namespace: Option<IgnoredAny>,
+    compiler_babel_options: Option<IgnoredAny>,
+    experimental: Option<IgnoredAny>,
+    extractor_parser_options: Option<IgnoredAny>,
+    extractors: Option<IgnoredAny>,
+    format: Option<IgnoredAny>,
+    format_options: Option<IgnoredAny>,
+    order_by: Option<IgnoredAny>,
+    root_dir: Option<IgnoredAny>,
+    service: Option<IgnoredAny>,
+}
+
+/// ["@lingui/core", "i18n"] or { "i18n": [...], "Trans": [...] }
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged, expecting = "expected [module, export] or an object with i18n and Trans")]
+enum LinguiRuntimeConfigModule {
+    I18n(RuntimeModulesConfig),
+    Map {
+        i18n: Option<RuntimeModulesConfig>,
+        #[serde(rename = "Trans")]
+        trans: Option<RuntimeModulesConfig>,
+    },
+}
+
+impl LinguiConfig {
+    fn into_js_options(self) -> LinguiJsOptions {
+        let runtime_modules = self.runtime_config_module.map(|module| match module {
+            LinguiRuntimeConfigModule::I18n(i18n) => RuntimeModulesConfigMap {
+                i18n: Some(i18n),
+                trans: None,
+                i18n_getter: None,
+            },
+            LinguiRuntimeConfigModule::Map { i18n, trans } => RuntimeModulesConfigMap {
+                i18n,
+                trans,
+                i18n_getter: None,
+            },
+        });
+
+        LinguiJsOptions {
+            runtime_modules,
+            ..Default::default()
+        }
+    }
+}
+
+/// SWC mounts the project cwd as `/cwd` for WASI plugins
+fn config_dir(cwd: &str) -> &Path {
+    if cfg!(target_os = "wasi") {
+        Path::new("/cwd")
+    } else {
+        Path::new(cwd)
+    }
+}
+
+/// Merge maps by keys, values with the same key are merged as well
+fn merge_map<T>(
+    base: Option<HashMap<String, T>>,
+    other: Option<HashMap<String, T>>,
+    merge: fn(T, T) -> T,
+) -> Option<HashMap<String, T>> {
+    match (base, other) {
+        (Some(mut base), Some(other)) => {
+            for (key, value) in other {
+                let value = match base.remove(&key) {
+                    Some(base_value) => merge(base_value, value),
+                    None => value,
+                };
+
+                base.insert(key, value);
+            }
+
+            Some(base)
+        }
+        (base, other) => other.or(base),
+    }
 }
 
+/// Options applied to files matched by globs, later overrides take precedence
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(try_from = "serde_json::Map<String, serde_json::Value>")]
+struct LinguiJsOverride {
+    files: Vec<String>,
+    options: LinguiJsOptions,
+}
+
+/// `#[serde(flatten)]` doesn't support `deny_unknown_fields`, so options are split manually
+impl TryFrom<serde_json::Map<String, serde_json::Value>> for LinguiJsOverride {
+    type Error = String;
+
+    fn try_from(mut map: serde_json::Map<String, serde_json::Value>) -> Result<LinguiJsOverride, String> {
+        let files = map.remove("files").ok_or("missing field `files`")?;
+        let files = serde_json::from_value(files).map_err(|e| format!("at `files`: {e}"))?;
+
+        let options = serde_path_to_error::deserialize(serde_json::Value::Object(map)).map_err(|e| {
+            match e.path().to_string().as_str() {
+                "." => e.inner().to_string(),
+                path => format!("at `{path}`: {}", e.inner()),
+            }
+        })?;
+
+        Ok(LinguiJsOverride { files, options })
+    }
+}
+
+/// Globs are matched against a path relative to the cwd, `*` doesn't match `/`
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        builder.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+    }
+
+    builder.build()
+}
+
+/// Error in the plugin config reported to the user
+#[derive(Debug, PartialEq)]
+pub struct ConfigError {
+    pub message: String,
+    /// Hint how to fix the config, e.g. a suggestion for a misspelled option
+    pub note: Option<String>,
+}
+
+impl ConfigError {
+    fn new(message: String) -> ConfigError {
+        ConfigError { message, note: None }
+    }
+}
+
+static UNKNOWN_FIELD: Lazy<Regex> = Lazy::new(|| Regex::new(r"unknown field `([^`]+)`, expected (.+)").unwrap());
+static FIELD_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`]+)`").unwrap());
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(prev + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Suggest the closest known field for a misspelled one: `runtimeModule` -> `runtimeModules`
+fn suggest_field(message: &str) -> Option<String> {
+    let captures = UNKNOWN_FIELD.captures(message)?;
+    let unknown = &captures[1];
+
+    FIELD_NAME.captures_iter(&captures[2])
+        .map(|field| field[1].to_string())
+        .map(|field| (edit_distance(&unknown.to_lowercase(), &field.to_lowercase()), field))
+        .filter(|(distance, field)| *distance <= (field.len() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| format!("did you mean `{field}`?"))
+}
+
+/// Path of the file relative to the cwd with `/` separators
+pub fn relative_path(filename: &str, cwd: &str) -> String {
+    let filename = filename.replace('\\', "/");
+    let cwd = cwd.replace('\\', "/");
+
+    match filename.strip_prefix(&cwd) {
+        Some(path) if cwd.ends_with('/') => path.into(),
+        Some(path) if path.starts_with('/') => path[1..].into(),
+        _ => filename,
+    }
+}
+
+/// Capture groups of the pattern matched against the file path joined with `.`
+/// "^src/features/([^/]+)/" for src/features/files/menu.js -> "files"
+fn namespace_from_path(pattern: &str, path: &str) -> Option<String> {
+    let re = Regex::new(pattern).expect("idNamespacePattern is validated on parse");
+
+    let captures = re.captures(path)?;
+    let namespace: Vec<&str> = captures.iter()
+        .skip(1)
+        .flatten()
+        .map(|m| m.as_str())
+        .filter(|m| !m.is_empty())
+        .collect();
+
+    if namespace.is_empty() {
+        None
+    } else {
+        Some(namespace.join("."))
+    }
+}
+
+/// [module, export] or [module, "*", member] for namespace imports
 #[derive(Deserialize, Debug, PartialEq)]
 struct RuntimeModulesConfig(
     String,
     #[serde(default)]
     Option<String>,
+    #[serde(default)]
+    Option<String>,
 );
 
+impl RuntimeModulesConfig {
+    fn to_export(&self, default_name: &str) -> RuntimeExport {
+        match self.1.as_deref() {
+            Some("*") => RuntimeExport::Namespace(self.2.clone().unwrap_or(default_name.into())),
+            Some(name) => name.into(),
+            None => default_name.into(),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct RuntimeModulesConfigMap {
     i18n: Option<RuntimeModulesConfig>,
     trans: Option<RuntimeModulesConfig>,
+    i18n_getter: Option<bool>,
+}
+
+impl RuntimeModulesConfigMap {
+    fn merge(self, other: RuntimeModulesConfigMap) -> RuntimeModulesConfigMap {
+        RuntimeModulesConfigMap {
+            i18n: other.i18n.or(self.i18n),
+            trans: other.trans.or(self.trans),
+            i18n_getter: other.i18n_getter.or(self.i18n_getter),
+        }
+    }
+
+    /// Missing entries are taken from the fallback
+    fn normalize(&self, fallback: &RuntimeModulesConfigMapNormalized) -> RuntimeModulesConfigMapNormalized {
+        RuntimeModulesConfigMapNormalized {
+            i18n: self.i18n.as_ref()
+                .map(|o| (o.0.clone(), o.to_export("i18n")))
+                .unwrap_or_else(|| fallback.i18n.clone()),
+            trans: self.trans.as_ref()
+                .map(|o| (o.0.clone(), o.to_export("Trans")))
+                .unwrap_or_else(|| fallback.trans.clone()),
+            i18n_getter: self.i18n_getter.unwrap_or(self.i18n.is_none() && fallback.i18n_getter),
+        }
+    }
+}
+
+/// How the runtime module exports i18n instance or Trans component
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuntimeExport {
+    /// import { i18n } from "@lingui/core"
+    Named(String),
+    /// import i18n from "./i18n"
+    Default,
+    /// import * as i18nModule from "./i18n", referenced as i18nModule.i18n
+    Namespace(String),
+}
+
+impl RuntimeExport {
+    /// Name of the local binding for the injected import
+    pub fn local_name(&self, default_name: &str) -> String {
+        match self {
+            RuntimeExport::Named(name) => name.clone(),
+            RuntimeExport::Default => default_name.into(),
+            RuntimeExport::Namespace(member) => format!("{member}Module"),
+        }
+    }
+}
+
+impl From<&str> for RuntimeExport {
+    fn from(name: &str) -> RuntimeExport {
+        match name {
+            "default" => RuntimeExport::Default,
+            _ => RuntimeExport::Named(name.into()),
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RuntimeModulesConfigMapNormalized {
-    pub i18n: (String, String),
-    pub trans: (String, String),
+    pub i18n: (String, RuntimeExport),
+    pub trans: (String, RuntimeExport),
+    /// i18n export is a function returning the instance, e.g. `getI18n()._(...)`
+    pub i18n_getter: bool,
+}
+
+impl Default for RuntimeModulesConfigMapNormalized {
+    fn default() -> RuntimeModulesConfigMapNormalized {
+        RuntimeModulesConfigMapNormalized {
+            i18n: ("@lingui/core".into(), "i18n".into()),
+            trans: ("@lingui/react".into(), "Trans".into()),
+            i18n_getter: false,
+        }
+    }
 }
 
 impl LinguiJsOptions {
+    /// Parse the plugin config strictly, errors point to the invalid field
+    pub fn parse(json: &str) -> Result<LinguiJsOptions, ConfigError> {
+        let deserializer = &mut serde_json::Deserializer::from_str(json);
+
+        let options: LinguiJsOptions = serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let path = e.path().to_string();
+            let message = e.inner().to_string();
+
+            ConfigError {
+                note: suggest_field(&message),
+                message: if path == "." {
+                    format!("invalid config for lingui-plugin: {message}")
+                } else {
+                    format!("invalid config for lingui-plugin at `{path}`: {message}")
+                },
+            }
+        })?;
+
+        options.validate()?;
+
+        Ok(options)
+    }
+
+    /// Check globs and patterns which could not be validated by the schema
+    fn validate(&self) -> Result<(), ConfigError> {
+        let globs = self.include.iter()
+            .chain(&self.exclude)
+            .chain(self.overrides.iter().flatten().map(|o| &o.files));
+
+        for patterns in globs {
+            build_glob_set(patterns)
+                .map_err(|e| ConfigError::new(format!("invalid glob in config for lingui-plugin: {e}")))?;
+        }
+
+        if let Some(pattern) = &self.id_namespace_pattern {
+            Regex::new(pattern)
+                .map_err(|e| ConfigError::new(format!("invalid idNamespacePattern in config for lingui-plugin: {e}")))?;
+        }
+
+        let nested = self.overrides.iter().flatten()
+            .map(|o| &o.options)
+            .chain(self.env.iter().flat_map(|env| env.values()));
+
+        for options in nested {
+            options.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Set options of other take precedence, objects are merged deeply, arrays are replaced
+    fn merge(self, other: LinguiJsOptions) -> LinguiJsOptions {
+        LinguiJsOptions {
+            lingui_config: other.lingui_config.or(self.lingui_config),
+            env: merge_map(self.env, other.env, LinguiJsOptions::merge),
+            strip_non_essential_fields: other.strip_non_essential_fields.or(self.strip_non_essential_fields),
+            keep_fields: other.keep_fields.or(self.keep_fields),
+            include: other.include.or(self.include),
+            exclude: other.exclude.or(self.exclude),
+            overrides: other.overrides.or(self.overrides),
+            runtime_modules: match (self.runtime_modules, other.runtime_modules) {
+                (Some(base), Some(other)) => Some(base.merge(other)),
+                (base, other) => other.or(base),
+            },
+            directive_runtime_modules: merge_map(
+                self.directive_runtime_modules,
+                other.directive_runtime_modules,
+                RuntimeModulesConfigMap::merge,
+            ),
+            jsx_passthrough_attrs: other.jsx_passthrough_attrs.or(self.jsx_passthrough_attrs),
+            lift_jsx_conditionals: other.lift_jsx_conditionals.or(self.lift_jsx_conditionals),
+            fold_constants: other.fold_constants.or(self.fold_constants),
+            annotate_messages: other.annotate_messages.or(self.annotate_messages),
+            pure_annotations: other.pure_annotations.or(self.pure_annotations),
+            id_namespace: other.id_namespace.or(self.id_namespace),
+            id_namespace_pattern: other.id_namespace_pattern.or(self.id_namespace_pattern),
+            add_origin: other.add_origin.or(self.add_origin),
+            pseudo_localization: other.pseudo_localization.or(self.pseudo_localization),
+            debug_markers: other.debug_markers.or(self.debug_markers),
+            bidi_isolation: other.bidi_isolation.or(self.bidi_isolation),
+            normalize_unicode: other.normalize_unicode.or(self.normalize_unicode),
+            message_manifest: other.message_manifest.or(self.message_manifest),
+            compile_messages: other.compile_messages.or(self.compile_messages),
+        }
+    }
+
+    /// Merge relevant fields of the project's `lingui.config.json` from the cwd if enabled,
+    /// plugin options take precedence
+    pub fn with_lingui_config(mut self, cwd: &str) -> Result<LinguiJsOptions, ConfigError> {
+        let file = match self.lingui_config.take() {
+            Some(LinguiConfigPath::Enabled(true)) => "lingui.config.json".into(),
+            Some(LinguiConfigPath::Path(path)) => path,
+            Some(LinguiConfigPath::Enabled(false)) | None => return Ok(self),
+        };
+
+        let path = config_dir(cwd).join(file);
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| ConfigError::new(format!("failed to read lingui config {}: {e}", path.display())))?;
+
+        let lingui_config = serde_json::from_str::<LinguiConfig>(&content)
+            .map_err(|e| ConfigError::new(format!("invalid lingui config {}: {e}", path.display())))?;
+
+        if lingui_config.r#macro.is_some() {
+            return Err(ConfigError::new(format!(
+                "`macro` in lingui config {} is not supported by lingui-plugin, macros are imported from \"@lingui/macro\" only",
+                path.display(),
+            )));
+        }
+
+        Ok(lingui_config.into_js_options().merge(self))
+    }
+
+    /// Merge options of `env` block for the active environment over the base options
+    pub fn for_env(mut self, env_name: &str) -> LinguiJsOptions {
+        match self.env.take().and_then(|mut env| env.remove(env_name)) {
+            Some(env_options) => self.merge(env_options).for_env(env_name),
+            None => self,
+        }
+    }
+
+    /// Apply `include`/`exclude` and `overrides` for the file path relative to the cwd
+    /// Return None if the file should not be transformed
+    pub fn for_file(mut self, path: Option<&str>) -> Option<LinguiJsOptions> {
+        let path = match path {
+            Some(path) => path,
+            None => return Some(self),
+        };
+
+        if let Some(include) = &self.include {
+            if !build_glob_set(include).expect("globs are validated on parse").is_match(path) {
+                return None;
+            }
+        }
+
+        if let Some(exclude) = &self.exclude {
+            if build_glob_set(exclude).expect("globs are validated on parse").is_match(path) {
+                return None;
+            }
+        }
+
+        for o in self.overrides.take().unwrap_or_default() {
+            if build_glob_set(&o.files).expect("globs are validated on parse").is_match(path) {
+                self = self.merge(o.options);
+            }
+        }
+
+        if self.id_namespace.is_none() {
+            self.id_namespace = self.id_namespace_pattern.as_ref()
+                .and_then(|pattern| namespace_from_path(pattern, path));
+        }
+
+        Some(self)
+    }
+
     pub fn to_options(self, env_name: &str) -> LinguiOptions {
+        // `env` blocks of matched overrides are merged here
+        let options = self.for_env(env_name);
+
+        let runtime_modules = options.runtime_modules.as_ref()
+            .map(|o| o.normalize(&Default::default()))
+            .unwrap_or_default();
+
+        let directive_runtime_modules = options.directive_runtime_modules.as_ref()
+            .map(|directives| directives.iter()
+                .map(|(directive, o)| (directive.clone(), o.normalize(&runtime_modules)))
+                .collect())
+            .unwrap_or_default();
+
         LinguiOptions {
-            strip_non_essential_fields: !(matches!(env_name, "development")),
-            runtime_modules: RuntimeModulesConfigMapNormalized {
-                i18n: (
-                    self.runtime_modules.as_ref()
-                        .and_then(|o| o.i18n.as_ref())
-                        .and_then(|o| Some(o.0.clone()))
-                        .unwrap_or("@lingui/core".into()),
-                    self.runtime_modules.as_ref()
-                        .and_then(|o| o.i18n.as_ref())
-                        .and_then(|o| o.1.clone())
-                        .unwrap_or("i18n".into()),
-                ),
-                trans: (
-                    self.runtime_modules.as_ref()
-                        .and_then(|o| o.trans.as_ref())
-                        .and_then(|o| Some(o.0.clone()))
-                        .unwrap_or("@lingui/react".into()),
-                    self.runtime_modules.as_ref()
-                        .and_then(|o| o.trans.as_ref())
-                        .and_then(|o| o.1.clone())
-                        .unwrap_or("Trans".into()),
-                ),
+            strip_non_essential_fields: match options.strip_non_essential_fields {
+                Some(StripNonEssentialFields::Bool(strip)) => strip,
+                Some(StripNonEssentialFields::Mode(StripMode::Auto)) | None => !(matches!(env_name, "development")),
+            },
+            keep_fields: options.keep_fields.unwrap_or_default(),
+            runtime_modules,
+            directive_runtime_modules,
+            jsx_passthrough_attrs: options.jsx_passthrough_attrs.unwrap_or_default(),
+            lift_jsx_conditionals: options.lift_jsx_conditionals.unwrap_or(false),
+            fold_constants: options.fold_constants.unwrap_or(false),
+            annotate_messages: options.annotate_messages.unwrap_or(false),
+            pure_annotations: options.pure_annotations.unwrap_or(false),
+            id_namespace: options.id_namespace,
+            add_origin: options.add_origin.unwrap_or(false),
+            pseudo_localization: match options.pseudo_localization {
+                Some(PseudoLocalizationConfig::Enabled(true)) => Some(Default::default()),
+                Some(PseudoLocalizationConfig::Options(PseudoLocalizationOptions { expansion, markers })) => {
+                    let defaults = PseudoLocalization::default();
+
+                    Some(PseudoLocalization {
+                        expansion: expansion.unwrap_or(defaults.expansion),
+                        markers: markers.unwrap_or(defaults.markers),
+                    })
+                }
+                Some(PseudoLocalizationConfig::Enabled(false)) | None => None,
             },
+            debug_markers: options.debug_markers,
+            bidi_isolation: match options.bidi_isolation {
+                Some(BidiIsolationConfig::Enabled(true)) => Some(Default::default()),
+                Some(BidiIsolationConfig::Options(BidiIsolationOptions { exclude })) => Some(BidiIsolation {
+                    exclude: exclude.unwrap_or_default(),
+                }),
+                Some(BidiIsolationConfig::Enabled(false)) | None => None,
+            },
+            normalize_unicode: options.normalize_unicode.unwrap_or(false),
+            message_manifest: options.message_manifest,
+            compile_messages: options.compile_messages.unwrap_or(false),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct LinguiOptions {
     pub strip_non_essential_fields: bool,
+    /// Fields kept in addition to the essential ones when stripping, e.g. `message`
+    pub keep_fields: Vec<String>,
     pub runtime_modules: RuntimeModulesConfigMapNormalized,
+    /// Runtime modules used in files starting with the directive, e.g. `"use server"`
+    pub directive_runtime_modules: HashMap<String, RuntimeModulesConfigMapNormalized>,
+    /// Extra attributes passed as is from macro to the runtime `Trans` component.
+    /// An entry ending with `*` matches any attribute with that prefix, e.g. `data-*`
+    pub jsx_passthrough_attrs: Vec<String>,
+    /// Turn conditionals with translatable branches inside `<Trans>` into ICU select
+    pub lift_jsx_conditionals: bool,
+    /// Put literals and module level constants into the message text instead of placeholders
+    pub fold_constants: bool,
+    /// Mark generated descriptors and `i18n._` calls with `/*i18n*/` comment for the extractor
+    pub annotate_messages: bool,
+    /// Mark generated `i18n._` calls with `/*#__PURE__*/` comment, so unused messages could be dropped
+    pub pure_annotations: bool,
+    /// Prefix of IDs generated from the message text, `files.Open` for "Open"
+    pub id_namespace: Option<String>,
+    /// Add `origin` with the file, line and column of the macro to the generated messages
+    pub add_origin: bool,
+    /// Rewrite the message text to accented and expanded form wrapped with markers
+    pub pseudo_localization: Option<PseudoLocalization>,
+    /// Prepend markers carrying the message ID to the message text
+    pub debug_markers: Option<DebugMarkers>,
+    /// Wrap value placeholders in the message with FSI and PDI isolation marks
+    pub bidi_isolation: Option<BidiIsolation>,
+    /// Normalize literal text of the message to NFC, so IDs don't depend on the input form
+    pub normalize_unicode: bool,
+    /// Expose IDs of messages generated in the module to the bundler
+    pub message_manifest: Option<MessageManifest>,
+    /// Pass the message precompiled to Lingui's compiled format, the message text is kept as ID
+    pub compile_messages: bool,
 }
 
-impl Default for LinguiOptions {
-    fn default() -> LinguiOptions {
-        LinguiOptions {
-            strip_non_essential_fields: false,
-            runtime_modules: RuntimeModulesConfigMapNormalized {
-                i18n: ("@lingui/core".into(), "i18n".into()),
-                trans: ("@lingui/react".into(), "Trans".into()),
-            },
+/// `"export"` for `export const __linguiMessageIds = [...]`
+/// or `"comment"` for leading `/*@lingui-message-ids [...]*/` comment
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageManifest {
+    Export,
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BidiIsolation {
+    /// Placeholder names left bare, e.g. `count` or `0` for positional ones
+    pub exclude: Vec<String>,
+}
+
+/// `"visible"` for "⟦id⟧ message" or `"zeroWidth"` for the ID encoded with zero-width characters
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugMarkers {
+    Visible,
+    ZeroWidth,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PseudoLocalization {
+    /// Length of the literal text is multiplied by this factor, `1.3` adds 30% of padding
+    pub expansion: f64,
+    /// Start and end markers, so truncated messages could be spotted
+    pub markers: (String, String),
+}
+
+impl Default for PseudoLocalization {
+    fn default() -> PseudoLocalization {
+        PseudoLocalization {
+            expansion: 1.3,
+            markers: ("[".into(), "]".into()),
         }
     }
 }
 
+impl LinguiOptions {
+    /// Is the field of message descriptor or `Trans` attribute kept in the output?
+    /// Shared by JS and JSX macros
+    pub fn should_keep_field(&self, name: &str) -> bool {
+        !self.strip_non_essential_fields
+            || ESSENTIAL_FIELDS.contains(&name)
+            || self.keep_fields.iter().any(|field| field == name)
+            // pseudo text, isolated placeholders and debug markers are passed only in the message
+            || (name == "message" && (
+                self.pseudo_localization.is_some() || self.bidi_isolation.is_some() || self.debug_markers.is_some()
+            ))
+    }
+
+    /// `key` is always passed through, so `<Trans>` could be used in lists
+    pub fn is_jsx_passthrough_attr(&self, name: &str) -> bool {
+        name == "key" || self.jsx_passthrough_attrs.iter().any(|pattern| {
+            match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == pattern,
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod lib_tests {
     use super::*;
+    use crate::tests::common::TempDir;
 
     #[test]
     fn test_config() {
@@ -92,9 +735,11 @@ mod lib_tests {
 
         assert_eq!(config, LinguiJsOptions {
             runtime_modules: Some(RuntimeModulesConfigMap {
-                i18n: Some(RuntimeModulesConfig("@lingui/core".into(), Some("i18n".into()))),
-                trans: Some(RuntimeModulesConfig("@lingui/react".into(), Some("Trans".into()))),
-            })
+                i18n: Some(RuntimeModulesConfig("@lingui/core".into(), Some("i18n".into()), None)),
+                trans: Some(RuntimeModulesConfig("@lingui/react".into(), Some("Trans".into()), None)),
+                i18n_getter: None,
+            }),
+            ..Default::default()
         })
     }
 
@@ -111,9 +756,442 @@ mod lib_tests {
 
         assert_eq!(config, LinguiJsOptions {
             runtime_modules: Some(RuntimeModulesConfigMap {
-                i18n: Some(RuntimeModulesConfig("@lingui/core".into(), None)),
+                i18n: Some(RuntimeModulesConfig("@lingui/core".into(), None, None)),
                 trans: None,
-            })
+                i18n_getter: None,
+            }),
+            ..Default::default()
         })
     }
+
+    #[test]
+    fn test_config_jsx_passthrough_attrs() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "jsxPassthroughAttrs": ["data-*", "className"]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert!(config.is_jsx_passthrough_attr("key"));
+        assert!(config.is_jsx_passthrough_attr("data-testid"));
+        assert!(config.is_jsx_passthrough_attr("className"));
+        assert!(!config.is_jsx_passthrough_attr("classNames"));
+        assert!(!config.is_jsx_passthrough_attr("style"));
+    }
+
+    #[test]
+    fn test_config_runtime_exports() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "runtimeModules": {
+                    "i18n": ["./i18n", "default"],
+                    "trans": ["@lingui/react", "*"]
+                }
+               }"#
+        )
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config.runtime_modules.i18n, ("./i18n".into(), RuntimeExport::Default));
+        assert_eq!(config.runtime_modules.trans, ("@lingui/react".into(), RuntimeExport::Namespace("Trans".into())));
+    }
+
+    #[test]
+    fn test_config_directive_runtime_modules() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "runtimeModules": {
+                    "trans": ["@lingui/react", "Trans"]
+                },
+                "directiveRuntimeModules": {
+                    "use server": {
+                        "i18n": ["./i18n-server", "getI18n"],
+                        "i18nGetter": true
+                    }
+                }
+               }"#
+        )
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config.directive_runtime_modules.get("use server"), Some(&RuntimeModulesConfigMapNormalized {
+            i18n: ("./i18n-server".into(), "getI18n".into()),
+            trans: ("@lingui/react".into(), "Trans".into()),
+            i18n_getter: true,
+        }));
+    }
+
+    #[test]
+    fn test_config_for_file() {
+        let config = || serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "exclude": ["**/__fixtures__/**"],
+                "runtimeModules": {
+                    "i18n": ["@lingui/core", "i18n"]
+                },
+                "overrides": [
+                    {
+                        "files": ["packages/mobile/**"],
+                        "runtimeModules": {
+                            "trans": ["@lingui/react-native", "Trans"]
+                        },
+                        "foldConstants": true
+                    },
+                    {
+                        "files": ["packages/mobile/legacy/*.js"],
+                        "foldConstants": false
+                    }
+                ]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin");
+
+        assert!(config().for_file(Some("packages/web/__fixtures__/a.js")).is_none());
+        assert!(config().for_file(None).is_some());
+
+        let web = config().for_file(Some("packages/web/a.js")).unwrap().to_options("development");
+        assert_eq!(web.runtime_modules.trans, ("@lingui/react".into(), "Trans".into()));
+        assert!(!web.fold_constants);
+
+        let mobile = config().for_file(Some("packages/mobile/a.js")).unwrap().to_options("development");
+        assert_eq!(mobile.runtime_modules.i18n, ("@lingui/core".into(), "i18n".into()));
+        assert_eq!(mobile.runtime_modules.trans, ("@lingui/react-native".into(), "Trans".into()));
+        assert!(mobile.fold_constants);
+
+        let legacy = config().for_file(Some("packages/mobile/legacy/a.js")).unwrap().to_options("development");
+        assert!(!legacy.fold_constants);
+
+        let nested = config().for_file(Some("packages/mobile/legacy/nested/a.js")).unwrap().to_options("development");
+        assert!(nested.fold_constants);
+    }
+
+    #[test]
+    fn test_config_include() {
+        let config = || serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "include": ["src/**"]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin");
+
+        assert!(config().for_file(Some("src/a.js")).is_some());
+        assert!(config().for_file(Some("scripts/a.js")).is_none());
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path("/repo/src/a.js", "/repo"), "src/a.js");
+        assert_eq!(relative_path("/repo/src/a.js", "/repo/"), "src/a.js");
+        assert_eq!(relative_path("C:\\repo\\src\\a.js", "C:\\repo"), "src/a.js");
+        assert_eq!(relative_path("/other/a.js", "/repo"), "/other/a.js");
+        assert_eq!(relative_path("src/a.js", "/repo"), "src/a.js");
+    }
+
+    #[test]
+    fn test_config_id_namespace() {
+        let config = || serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "idNamespacePattern": "^src/features/([^/]+)/(?:([^/]+)/)?",
+                "overrides": [
+                    {
+                        "files": ["src/shared/**"],
+                        "idNamespace": "common"
+                    }
+                ]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin");
+
+        let namespace = |path| config().for_file(Some(path)).unwrap().to_options("development").id_namespace;
+
+        assert_eq!(namespace("src/features/files/menu.js"), Some("files".into()));
+        assert_eq!(namespace("src/features/store/status/badge.js"), Some("store.status".into()));
+        assert_eq!(namespace("src/shared/button.js"), Some("common".into()));
+        assert_eq!(namespace("src/app.js"), None);
+    }
+
+    #[test]
+    fn test_config_strip_non_essential_fields() {
+        let strip = |config: &str, env_name: &str| serde_json::from_str::<LinguiJsOptions>(config)
+            .expect("invalid config for lingui-plugin")
+            .to_options(env_name)
+            .strip_non_essential_fields;
+
+        assert!(strip(r#"{}"#, "production"));
+        assert!(!strip(r#"{}"#, "development"));
+        assert!(strip(r#"{"stripNonEssentialFields": "auto"}"#, "test"));
+        assert!(!strip(r#"{"stripNonEssentialFields": false}"#, "production"));
+        assert!(strip(r#"{"stripNonEssentialFields": true}"#, "development"));
+    }
+
+    #[test]
+    fn test_config_keep_fields() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "keepFields": ["message"]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin")
+            .to_options("production");
+
+        assert!(config.should_keep_field("id"));
+        assert!(config.should_keep_field("message"));
+        assert!(!config.should_keep_field("comment"));
+    }
+
+    #[test]
+    fn test_config_pseudo_localization() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config(r#"{}"#).pseudo_localization, None);
+        assert_eq!(config(r#"{ "pseudoLocalization": false }"#).pseudo_localization, None);
+        assert_eq!(config(r#"{ "pseudoLocalization": true }"#).pseudo_localization, Some(PseudoLocalization::default()));
+        assert_eq!(config(r#"{ "pseudoLocalization": { "markers": ["<<", ">>"] } }"#).pseudo_localization, Some(PseudoLocalization {
+            expansion: 1.3,
+            markers: ("<<".into(), ">>".into()),
+        }));
+
+        assert!(LinguiJsOptions::parse(r#"{ "pseudoLocalization": { "expand": 2 } }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_debug_markers() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config(r#"{}"#).debug_markers, None);
+        assert_eq!(config(r#"{ "debugMarkers": "visible" }"#).debug_markers, Some(DebugMarkers::Visible));
+        assert_eq!(config(r#"{ "debugMarkers": "zeroWidth" }"#).debug_markers, Some(DebugMarkers::ZeroWidth));
+
+        assert!(LinguiJsOptions::parse(r#"{ "debugMarkers": true }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_bidi_isolation() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config(r#"{}"#).bidi_isolation, None);
+        assert_eq!(config(r#"{ "bidiIsolation": true }"#).bidi_isolation, Some(BidiIsolation::default()));
+        assert_eq!(config(r#"{ "bidiIsolation": { "exclude": ["count"] } }"#).bidi_isolation, Some(BidiIsolation {
+            exclude: vec!["count".into()],
+        }));
+
+        assert!(LinguiJsOptions::parse(r#"{ "bidiIsolation": { "excludes": [] } }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_normalize_unicode() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert!(!config(r#"{}"#).normalize_unicode);
+        assert!(config(r#"{ "normalizeUnicode": true }"#).normalize_unicode);
+    }
+
+    #[test]
+    fn test_config_message_manifest() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert_eq!(config(r#"{}"#).message_manifest, None);
+        assert_eq!(config(r#"{ "messageManifest": "export" }"#).message_manifest, Some(MessageManifest::Export));
+        assert_eq!(config(r#"{ "messageManifest": "comment" }"#).message_manifest, Some(MessageManifest::Comment));
+
+        assert!(LinguiJsOptions::parse(r#"{ "messageManifest": "json" }"#).is_err());
+    }
+
+    #[test]
+    fn test_config_compile_messages() {
+        let config = |json: &str| LinguiJsOptions::parse(json)
+            .expect("invalid config for lingui-plugin")
+            .to_options("development");
+
+        assert!(!config(r#"{}"#).compile_messages);
+        assert!(config(r#"{ "env": { "development": { "compileMessages": true } } }"#).compile_messages);
+    }
+
+    #[test]
+    fn test_config_env() {
+        let config = || serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "runtimeModules": {
+                    "i18n": ["./i18n", "i18n"],
+                    "trans": ["./trans", "Trans"]
+                },
+                "jsxPassthroughAttrs": ["data-*"],
+                "keepFields": ["message"],
+                "env": {
+                    "staging": {
+                        "runtimeModules": {
+                            "trans": ["./trans-staging", "Trans"]
+                        },
+                        "jsxPassthroughAttrs": ["className"],
+                        "stripNonEssentialFields": true,
+                        "foldConstants": true
+                    }
+                }
+               }"#
+        )
+            .expect("invalid config for lingui-plugin");
+
+        let staging = config().to_options("staging");
+        // objects are merged deeply
+        assert_eq!(staging.runtime_modules.i18n, ("./i18n".into(), "i18n".into()));
+        assert_eq!(staging.runtime_modules.trans, ("./trans-staging".into(), "Trans".into()));
+        // arrays are replaced
+        assert!(staging.is_jsx_passthrough_attr("className"));
+        assert!(!staging.is_jsx_passthrough_attr("data-testid"));
+        // scalars are overridden, unset options are kept
+        assert!(staging.strip_non_essential_fields);
+        assert!(staging.fold_constants);
+        assert_eq!(staging.keep_fields, vec!["message".to_string()]);
+
+        let development = config().to_options("development");
+        assert_eq!(development.runtime_modules.trans, ("./trans".into(), "Trans".into()));
+        assert!(development.is_jsx_passthrough_attr("data-testid"));
+        assert!(!development.strip_non_essential_fields);
+        assert!(!development.fold_constants);
+    }
+
+    #[test]
+    fn test_config_env_in_overrides() {
+        let config = || serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "env": {
+                    "production": {
+                        "foldConstants": true,
+                        "directiveRuntimeModules": {
+                            "use server": { "i18n": ["./i18n-server", "getI18n"] }
+                        }
+                    }
+                },
+                "overrides": [
+                    {
+                        "files": ["legacy/**"],
+                        "env": {
+                            "production": {
+                                "foldConstants": false,
+                                "directiveRuntimeModules": {
+                                    "use server": { "i18nGetter": true }
+                                }
+                            }
+                        }
+                    }
+                ]
+               }"#
+        )
+            .expect("invalid config for lingui-plugin");
+
+        let options = |path| config()
+            .for_env("production")
+            .for_file(Some(path))
+            .unwrap()
+            .to_options("production");
+
+        let app = options("app/a.js");
+        assert!(app.fold_constants);
+        assert!(!app.directive_runtime_modules["use server"].i18n_getter);
+
+        let legacy = options("legacy/a.js");
+        assert!(!legacy.fold_constants);
+        assert_eq!(legacy.directive_runtime_modules["use server"].i18n, ("./i18n-server".into(), "getI18n".into()));
+        assert!(legacy.directive_runtime_modules["use server"].i18n_getter);
+    }
+
+    #[test]
+    fn test_config_lingui_config() {
+        let dir = TempDir::new("test-config-lingui-config");
+        let cwd = dir.path();
+        fs::write(cwd.join("lingui.config.json"), r#"{
+            "locales": ["en", "cs"],
+            "sourceLocale": "en",
+            "runtimeConfigModule": { "i18n": ["./i18n", "i18n"], "Trans": ["./trans", "Trans"] }
+        }"#).unwrap();
+        fs::write(cwd.join("lingui.short.json"), r#"{
+            "runtimeConfigModule": ["./short-i18n"]
+        }"#).unwrap();
+        fs::write(cwd.join("lingui.invalid.json"), r#"{ "runtimeConfigModule": 1 }"#).unwrap();
+        fs::write(cwd.join("lingui.unknown.json"), r#"{ "runtimeConfigModules": ["./i18n"] }"#).unwrap();
+        fs::write(cwd.join("lingui.macro.json"), r#"{ "macro": { "corePackage": ["./my-macro"] } }"#).unwrap();
+
+        let cwd = cwd.to_str().unwrap();
+        let load = |config: &str| serde_json::from_str::<LinguiJsOptions>(config)
+            .expect("invalid config for lingui-plugin")
+            .with_lingui_config(cwd)
+            .map(|options| options.to_options("development"));
+
+        let options = load(r#"{
+            "linguiConfig": true,
+            "runtimeModules": { "trans": ["@lingui/react", "Trans"] }
+        }"#).unwrap();
+        assert_eq!(options.runtime_modules.i18n, ("./i18n".into(), "i18n".into()));
+        // plugin options take precedence
+        assert_eq!(options.runtime_modules.trans, ("@lingui/react".into(), "Trans".into()));
+
+        let options = load(r#"{ "linguiConfig": "lingui.short.json" }"#).unwrap();
+        assert_eq!(options.runtime_modules.i18n, ("./short-i18n".into(), "i18n".into()));
+        assert_eq!(options.runtime_modules.trans, ("@lingui/react".into(), "Trans".into()));
+
+        let options = load(r#"{ "linguiConfig": false }"#).unwrap();
+        assert_eq!(options.runtime_modules.i18n, ("@lingui/core".into(), "i18n".into()));
+
+        assert!(load(r#"{ "linguiConfig": "missing.json" }"#).unwrap_err().message.starts_with("failed to read lingui config"));
+        assert!(load(r#"{ "linguiConfig": "lingui.invalid.json" }"#).unwrap_err().message.starts_with("invalid lingui config"));
+
+        let e = load(r#"{ "linguiConfig": "lingui.unknown.json" }"#).unwrap_err();
+        assert!(e.message.contains("unknown field `runtimeConfigModules`"), "{}", e.message);
+
+        let e = load(r#"{ "linguiConfig": "lingui.macro.json" }"#).unwrap_err();
+        assert!(e.message.starts_with("`macro` in lingui config"), "{}", e.message);
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let error = |config: &str| LinguiJsOptions::parse(config).unwrap_err();
+
+        let e = error(r#"{ "runtimeModule": {} }"#);
+        assert!(e.message.starts_with("invalid config for lingui-plugin at `runtimeModule`: unknown field `runtimeModule`"), "{}", e.message);
+        assert_eq!(e.note, Some("did you mean `runtimeModules`?".into()));
+
+        let e = error(r#"{ "runtimeModules": { "i18n": ["@lingui/core", 1] } }"#);
+        assert!(e.message.starts_with("invalid config for lingui-plugin at `runtimeModules.i18n[1]`: invalid type: integer `1`"), "{}", e.message);
+
+        let e = error(r#"{ "runtimeModules": { "Trans": ["@lingui/react"] } }"#);
+        assert!(e.message.contains("unknown field `Trans`"), "{}", e.message);
+        assert_eq!(e.note, Some("did you mean `trans`?".into()));
+
+        let e = error(r#"{ "stripNonEssentialFields": "yes" }"#);
+        assert!(e.message.starts_with(r#"invalid config for lingui-plugin at `stripNonEssentialFields`: expected true, false or "auto""#), "{}", e.message);
+
+        let e = error(r#"{ "env": { "production": { "foldConstant": true } } }"#);
+        assert!(e.message.contains("unknown field `foldConstant`"), "{}", e.message);
+        assert_eq!(e.note, Some("did you mean `foldConstants`?".into()));
+
+        let e = error(r#"{ "overrides": [{ "files": ["a/**"], "pureAnnotation": true }] }"#);
+        assert!(e.message.starts_with("invalid config for lingui-plugin at `overrides[0]`: at `pureAnnotation`: unknown field `pureAnnotation`"), "{}", e.message);
+        assert_eq!(e.note, Some("did you mean `pureAnnotations`?".into()));
+
+        let e = error(r#"{ "overrides": [{ "foldConstants": true }] }"#);
+        assert!(e.message.starts_with("invalid config for lingui-plugin at `overrides[0]`: missing field `files`"), "{}", e.message);
+
+        let e = error(r#"{ "somethingElse": true }"#);
+        assert_eq!(e.note, None);
+
+        let e = error(r#"{ "overrides": [{ "files": ["a/[**"] }] }"#);
+        assert!(e.message.starts_with("invalid glob in config for lingui-plugin"), "{}", e.message);
+
+        let e = error(r#"{ "env": { "test": { "idNamespacePattern": "(" } } }"#);
+        assert!(e.message.starts_with("invalid idNamespacePattern in config for lingui-plugin"), "{}", e.message);
+
+        assert!(LinguiJsOptions::parse(r#"{ "overrides": [{ "files": ["a/**"], "foldConstants": true }] }"#).is_ok());
+    }
 }
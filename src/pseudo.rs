@@ -0,0 +1,46 @@
+use regex::{Regex};
+use once_cell::sync::Lazy;
+use crate::options::PseudoLocalization;
+
+// html entities and escape sequences are kept as is: &nbsp; \n é \x41
+static PROTECTED_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"&[#\w]+;|\\u\{[0-9a-fA-F]+\}|\\u[0-9a-fA-F]{4}|\\x[0-9a-fA-F]{2}|\\.").unwrap());
+
+const PLAIN: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const ACCENTED: &str = "ÅƁÇĐÉƑĜĤÎĴĶĻṀÑÖÞǪŔŠŢÛṼŴẊÝŽåƀçđéƒĝĥîĵķļṁñöþǫŕšţûṽŵẋýž";
+
+fn accent_char(ch: char) -> char {
+    PLAIN.chars()
+        .position(|plain| plain == ch)
+        .and_then(|index| ACCENTED.chars().nth(index))
+        .unwrap_or(ch)
+}
+
+/// Replace latin letters of the message text with accented ones: "Hello" -> "Ĥéļļö"
+pub fn accent(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() * 2);
+    let mut last = 0;
+
+    for protected in PROTECTED_RE.find_iter(text) {
+        result.extend(text[last..protected.start()].chars().map(accent_char));
+        result.push_str(protected.as_str());
+        last = protected.end();
+    }
+
+    result.extend(text[last..].chars().map(accent_char));
+    result
+}
+
+/// Count of characters which are expanded by pseudo localization
+pub fn text_len(text: &str) -> usize {
+    PROTECTED_RE.replace_all(text, "").chars().filter(|ch| ch.is_alphanumeric()).count()
+}
+
+/// Pad the message according to the expansion factor and wrap it with markers:
+/// "Ĥéļļö {name}" -> "[Ĥéļļö {name}~~]"
+/// `text_len` is a count of characters in the literal text, placeholders are not expanded
+pub fn wrap(message: &str, text_len: usize, pseudo: &PseudoLocalization) -> String {
+    let padding = (text_len as f64 * (pseudo.expansion - 1.0)).round().max(0.0) as usize;
+    let (start, end) = &pseudo.markers;
+
+    format!("{start}{message}{}{end}", "~".repeat(padding))
+}
@@ -0,0 +1,112 @@
+use crate::to;
+use crate::LinguiOptions;
+use crate::options::BidiIsolation;
+
+to!(
+    js_should_isolate_placeholders,
+    LinguiOptions {
+        bidi_isolation: Some(BidiIsolation::default()),
+        ..Default::default()
+    },
+    r##"
+     import { t, plural } from "@lingui/macro";
+     t`Hello ${name}, you have ${user.messages} messages`;
+     plural(count, { one: `# book by ${author}`, other: "# books" });
+     "##,
+    "
+     import { i18n } from \"@lingui/core\";
+     i18n._({
+       id: \"Hello {name}, you have {0} messages\",
+       message: \"Hello \u{2068}{name}\u{2069}, you have \u{2068}{0}\u{2069} messages\",
+       values: {
+         name: name,
+         0: user.messages
+       }
+     });
+     i18n._({
+       id: \"{count, plural, one {# book by {author}} other {# books}}\",
+       message: \"{count, plural, one {# book by \u{2068}{author}\u{2069}} other {# books}}\",
+       values: {
+         count: count,
+         author: author
+       }
+     });
+     "
+);
+
+to!(
+    js_should_not_isolate_excluded_placeholders,
+    LinguiOptions {
+        bidi_isolation: Some(BidiIsolation {
+            exclude: vec!["count".into(), "0".into()],
+        }),
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/macro";
+     t`${count} messages from ${name}, ${user.age}`;
+     "#,
+    "
+     import { i18n } from \"@lingui/core\";
+     i18n._({
+       id: \"{count} messages from {name}, {0}\",
+       message: \"{count} messages from \u{2068}{name}\u{2069}, {0}\",
+       values: {
+         count: count,
+         name: name,
+         0: user.age
+       }
+     });
+     "
+);
+
+to!(
+    jsx_should_isolate_placeholders_but_not_tags,
+    LinguiOptions {
+        bidi_isolation: Some(BidiIsolation::default()),
+        ..Default::default()
+    },
+    r#"
+     import { Trans } from "@lingui/macro";
+     <Trans>Hello <b>{name}</b>!</Trans>;
+     "#,
+    "
+     import { Trans } from \"@lingui/react\";
+     <Trans id={\"Hello <0>{name}</0>!\"} message={\"Hello <0>\u{2068}{name}\u{2069}</0>!\"} values={{
+       name: name
+     }} components={{
+       0: <b />
+     }} />;
+     "
+);
+
+to!(
+    js_should_isolate_placeholders_in_pseudo_text,
+    LinguiOptions {
+        bidi_isolation: Some(BidiIsolation::default()),
+        pseudo_localization: Some(Default::default()),
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/macro";
+     t`Hi ${name}`;
+     t`${name}`;
+     "#,
+    "
+     import { i18n } from \"@lingui/core\";
+     i18n._({
+       id: \"Hi {name}\",
+       message: \"[\u{124}\\xee \u{2068}{name}\u{2069}~]\",
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: \"{name}\",
+       message: \"\u{2068}{name}\u{2069}\",
+       values: {
+         name: name
+       }
+     });
+     "
+);
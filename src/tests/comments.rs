@@ -0,0 +1,107 @@
+use crate::LinguiOptions;
+use crate::tests::common::Transformed;
+
+// comments are dropped by the `to!` harness
+
+#[test]
+fn js_comments_are_moved_to_replacement() {
+    assert_eq!(Transformed::new(r#"// Licence header
+import { t, defineMessage } from "@lingui/macro";
+
+const a = /* webpackIgnore */ t`Hello ${name}`; // eslint-disable-line
+const b = defineMessage(/** Greeting */ {
+  /** Shown on the main page */
+  message: "Hi",
+});
+"#, Default::default()).print(), r#"// Licence header
+import { i18n } from "@lingui/core";
+const a = /* webpackIgnore */ i18n._("Hello {name}", {
+    name: name
+}); // eslint-disable-line
+const b = /** Greeting */ {
+    /** Shown on the main page */ id: "Hi"
+};
+"#);
+}
+
+#[test]
+fn comments_of_unused_macro_import_are_kept() {
+    assert_eq!(Transformed::new(r#"// @ts-nocheck
+import { t } from "@lingui/macro";
+import foo from "bar";
+"#, Default::default()).print(), r#"// @ts-nocheck
+import foo from "bar";
+"#);
+}
+
+#[test]
+fn jsx_comments_are_moved_to_replacement() {
+    // JSX elements don't print leading comments, they are moved to the start of the statement
+    assert_eq!(Transformed::new(r#"import { Trans } from "@lingui/macro";
+
+const a = (
+  // eslint-disable-next-line
+  <Trans>Hello</Trans>
+);
+const b = () => {
+  return (
+    /* translators: greeting */
+    <Trans>Hi</Trans>
+  );
+};
+"#, Default::default()).print(), r#"import { Trans } from "@lingui/react";
+// eslint-disable-next-line
+const a = (<Trans id={"Hello"}/>);
+const b = ()=>{
+    /* translators: greeting */ return (<Trans id={"Hi"}/>);
+};
+"#);
+}
+
+#[test]
+fn jsx_comments_on_preserved_attributes_are_kept() {
+    assert_eq!(Transformed::new(r#"import { Plural } from "@lingui/macro";
+<Plural
+  value={count}
+  /* translators context */
+  context="library"
+  one="Book"
+  other="Books"
+/>;
+"#, Default::default()).print(), r#"import { Trans } from "@lingui/react";
+<Trans id={"{count, plural, one {Book} other {Books}}"} values={{
+    count: count
+}} /* translators context */ context="library"/>;
+"#);
+}
+
+#[test]
+fn messages_are_annotated_for_extractor() {
+    let code = r##"import { t, plural, defineMessage } from "@lingui/macro";
+const a = t`Hello ${name}`;
+const b = plural(count, { one: "# book", other: "# books" });
+const c = defineMessage({ message: "Hi" });
+const d = t({ id: "bye", message: "Bye" });
+"##;
+
+    assert_eq!(Transformed::new(code, LinguiOptions {
+        annotate_messages: true,
+        ..Default::default()
+    }).print(), r#"import { i18n } from "@lingui/core";
+const a = i18n._(/*i18n*/ "Hello {name}", {
+    name: name
+});
+const b = i18n._(/*i18n*/ "{count, plural, one {# book} other {# books}}", {
+    count: count
+});
+const c = /*i18n*/ {
+    id: "Hi"
+};
+const d = i18n._(/*i18n*/ {
+    id: "bye",
+    message: "Bye"
+});
+"#);
+
+    assert!(!Transformed::new(code, Default::default()).print().contains("/*i18n*/"));
+}
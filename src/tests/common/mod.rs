@@ -1,3 +1,140 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use swc_core::{
+    common::{
+        comments::SingleThreadedComments,
+        errors::{DiagnosticBuilder, Emitter as DiagnosticEmitter, Handler},
+        FileName, Globals, GLOBALS, Mark, SourceMap, sync::Lrc,
+    },
+    ecma::{
+        ast::Module,
+        codegen::{text_writer::JsWriter, Emitter},
+        parser::{parse_file_as_module, Syntax, TsConfig},
+        transforms::base::resolver,
+        visit::FoldWith,
+    },
+    plugin::errors::HANDLER,
+};
+use crate::{LinguiMacroFolder, LinguiOptions};
+
+/// Messages of diagnostics reported through `HANDLER` by all tests
+static DIAGNOSTICS: Lazy<Mutex<Vec<String>>> = Lazy::new(Default::default);
+
+struct CollectingEmitter;
+
+impl DiagnosticEmitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        DIAGNOSTICS.lock().unwrap().push(db.message());
+    }
+}
+
+/// `HANDLER` is global and set once for all tests
+pub fn init_handler() {
+    if HANDLER.inner.set(Handler::with_emitter(true, false, Box::new(CollectingEmitter))).is_err() {
+        // set on a previous run
+    }
+}
+
+pub fn diagnostics() -> Vec<String> {
+    DIAGNOSTICS.lock().unwrap().clone()
+}
+
+/// Directory for config fixtures, unique per test and process, removed on drop
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("lingui-plugin-{name}-{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Code transformed outside of the `test!` harness,
+/// for assertions on spans and comments which are dropped by the harness
+pub struct Transformed {
+    pub cm: Lrc<SourceMap>,
+    pub comments: SingleThreadedComments,
+    pub module: Module,
+    /// marks are valid only within these globals
+    pub globals: Globals,
+    pub unresolved_mark: Mark,
+    pub top_level_mark: Mark,
+}
+
+impl Transformed {
+    pub fn new(code: &str, options: LinguiOptions) -> Transformed {
+        Transformed::with_fold(code, |module, comments, source_map| {
+            module.fold_with(&mut LinguiMacroFolder::new(options)
+                .with_comments(comments.clone())
+                .with_source_map("src/App.js".into(), move |pos| source_map.lookup_char_pos(pos)))
+        })
+    }
+
+    /// Code parsed and resolved, then transformed by the given function
+    pub fn with_fold<F>(code: &str, fold: F) -> Transformed
+        where F: FnOnce(Module, &SingleThreadedComments, Lrc<SourceMap>) -> Module
+    {
+        let cm: Lrc<SourceMap> = Default::default();
+        let comments = SingleThreadedComments::default();
+        let fm = cm.new_source_file(FileName::Anon, code.into());
+
+        let module = parse_file_as_module(
+            &fm,
+            Syntax::Typescript(TsConfig { tsx: true, ..Default::default() }),
+            Default::default(),
+            Some(&comments),
+            &mut vec![],
+        ).expect("failed to parse");
+
+        let globals = Globals::default();
+        let source_map = cm.clone();
+
+        let (module, unresolved_mark, top_level_mark) = GLOBALS.set(&globals, || {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let module = fold(
+                module.fold_with(&mut resolver(unresolved_mark, top_level_mark, true)),
+                &comments,
+                source_map,
+            );
+
+            (module, unresolved_mark, top_level_mark)
+        });
+
+        Transformed { cm, comments, module, globals, unresolved_mark, top_level_mark }
+    }
+
+    pub fn print(&self) -> String {
+        let mut buf = vec![];
+
+        Emitter {
+            cfg: Default::default(),
+            cm: self.cm.clone(),
+            comments: Some(&self.comments),
+            wr: JsWriter::new(self.cm.clone(), "\n", &mut buf, None),
+        }
+            .emit_module(&self.module)
+            .expect("failed to print");
+
+        String::from_utf8(buf).unwrap()
+    }
+}
+
 #[macro_export]
 macro_rules! to {
     ($name:ident, $from:expr, $to:expr) => {
@@ -7,16 +144,7 @@ macro_rules! to {
                 ..Default::default()
             }),
             |_| {
-                if let Err(_) = swc_core::plugin::errors::HANDLER.inner.set(
-                        swc_core::common::errors::Handler::with_tty_emitter(
-                            swc_core::common::errors::ColorConfig::Auto,
-                            true,
-                            false,
-                            None,
-                        )
-                ) {
-                    // set on a previous run
-                }
+                $crate::tests::common::init_handler();
 
                 swc_core::common::chain!(
                     swc_core::ecma::transforms::base::resolver(swc_core::common::Mark::new(), swc_core::common::Mark::new(), true),
@@ -37,16 +165,7 @@ macro_rules! to {
                 ..Default::default()
             }),
             |_| {
-                if let Err(_) = swc_core::plugin::errors::HANDLER.inner.set(
-                        swc_core::common::errors::Handler::with_tty_emitter(
-                            swc_core::common::errors::ColorConfig::Auto,
-                            true,
-                            false,
-                            None,
-                        )
-                ) {
-                    // set on a previous run
-                }
+                $crate::tests::common::init_handler();
 
                 swc_core::common::chain!(
                     swc_core::ecma::transforms::base::resolver(swc_core::common::Mark::new(), swc_core::common::Mark::new(), true),
@@ -62,5 +181,26 @@ macro_rules! to {
             $to,
             ok_if_code_eq
         );
-    }
+    };
+
+    ($name:ident, $options:expr, $from:expr, $to:expr) => {
+        swc_core::ecma::transforms::testing::test!(
+            swc_core::ecma::parser::Syntax::Typescript(swc_core::ecma::parser::TsConfig {
+                tsx: true,
+                ..Default::default()
+            }),
+            |_| {
+                $crate::tests::common::init_handler();
+
+                swc_core::common::chain!(
+                    swc_core::ecma::transforms::base::resolver(swc_core::common::Mark::new(), swc_core::common::Mark::new(), true),
+                    $crate::LinguiMacroFolder::new($options)
+                )
+            },
+            $name,
+            $from,
+            $to,
+            ok_if_code_eq
+        );
+    };
 }
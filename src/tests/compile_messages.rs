@@ -0,0 +1,147 @@
+use crate::to;
+use crate::icu::{compile_message, CompiledMessage, CompiledToken};
+
+to!(
+    js_should_compile_messages,
+    crate::LinguiOptions {
+        compile_messages: true,
+        ..Default::default()
+    },
+    r##"
+     import { t, plural, defineMessage } from "@lingui/macro";
+
+     t`Hello`;
+     t`Hello ${name}`;
+     plural(count, { offset: 1, "=0": "No books", one: `# book by ${author}`, other: "# books" });
+     t({ id: "msg.id", message: `Hi ${name}` });
+     const msg = defineMessage({ message: "Bye" });
+     "##,
+    r##"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "Hello",
+       message: "Hello"
+     });
+     i18n._({
+       id: "Hello {name}",
+       message: ["Hello ", ["name"]],
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: "{count, plural, offset:1 =0 {No books} one {# book by {author}} other {# books}}",
+       message: [["count", "plural", {
+         offset: 1,
+         "0": "No books",
+         one: ["#", " book by ", ["author"]],
+         other: ["#", " books"]
+       }]],
+       values: {
+         count: count,
+         author: author
+       }
+     });
+     i18n._({
+       id: "msg.id",
+       message: ["Hi ", ["name"]],
+       values: {
+         name: name
+       }
+     });
+     const msg = {
+       id: "Bye",
+       message: "Bye"
+     };
+     "##
+);
+
+to!(
+    jsx_should_compile_messages,
+    crate::LinguiOptions {
+        compile_messages: true,
+        ..Default::default()
+    },
+    r#"
+     import { Trans, Select } from "@lingui/macro";
+
+     <Trans>Hello <b>{name}</b></Trans>;
+     <Trans id="custom.id">Hello</Trans>;
+     <Select value={gender} _male="He" _female="She" other="They" />;
+     "#,
+    r#"
+     import { Trans } from "@lingui/react";
+
+     <Trans id={"Hello <0>{name}</0>"} message={["Hello <0>", ["name"], "</0>"]} values={{
+       name: name
+     }} components={{
+       0: <b />
+     }} />;
+     <Trans message={"Hello"} id="custom.id" />;
+     <Trans id={"{gender, select, male {He} female {She} other {They}}"} message={[["gender", "select", {
+       male: "He",
+       female: "She",
+       other: "They"
+     }]]} values={{
+       gender: gender
+     }} />;
+     "#
+);
+
+to!(
+    js_should_not_compile_stripped_messages,
+    crate::LinguiOptions {
+        compile_messages: true,
+        strip_non_essential_fields: true,
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/macro";
+
+     t`Hello ${name}`;
+     "#,
+    r#"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "Hello {name}",
+       values: {
+         name: name
+       }
+     });
+     "#
+);
+
+#[test]
+fn compile_message_formats_and_escapes() {
+    assert_eq!(compile_message("It''s '{'name'}' {price, number, ::currency/EUR} {n, number}"), Ok(CompiledMessage::Tokens(vec![
+        CompiledToken::Text("It's {name} ".into()),
+        CompiledToken::Function("price".into(), "number".into(), Some("::currency/EUR".into())),
+        CompiledToken::Text(" ".into()),
+        CompiledToken::Function("n".into(), "number".into(), None),
+    ])));
+}
+
+#[test]
+fn compile_message_octothorpe_only_in_plural() {
+    assert_eq!(compile_message("#1 {n, select, a {#} other {x}}"), Ok(CompiledMessage::Tokens(vec![
+        CompiledToken::Text("#1 ".into()),
+        CompiledToken::Choice {
+            arg: "n".into(),
+            kind: "select".into(),
+            offset: None,
+            cases: vec![
+                ("a".into(), CompiledMessage::Text("#".into())),
+                ("other".into(), CompiledMessage::Text("x".into())),
+            ],
+        },
+    ])));
+}
+
+#[test]
+fn compile_message_errors() {
+    assert!(compile_message("Hello {name").is_err());
+    assert!(compile_message("Hello }").is_err());
+    assert!(compile_message("{n, plural, one {book}").is_err());
+}
@@ -0,0 +1,106 @@
+use crate::to;
+use crate::{debug_markers::mark, icu::{compile_message, CompiledMessage}, options::DebugMarkers};
+
+to!(
+    js_should_add_visible_debug_markers,
+    crate::LinguiOptions {
+        debug_markers: Some(DebugMarkers::Visible),
+        ..Default::default()
+    },
+    r#"
+     import { t, defineMessage } from "@lingui/macro";
+
+     t`Hello ${name}`;
+     t({ id: "greeting", message: "Hello" });
+     const msg = defineMessage({ message: "Bye" });
+     "#,
+    r#"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "Hello {name}",
+       message: "⟦Hello '{name}'⟧ Hello {name}",
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: "greeting",
+       message: "⟦greeting⟧ Hello"
+     });
+     const msg = {
+       id: "Bye",
+       message: "⟦Bye⟧ Bye"
+     };
+     "#
+);
+
+to!(
+    js_should_add_debug_markers_with_namespace,
+    crate::LinguiOptions {
+        debug_markers: Some(DebugMarkers::Visible),
+        id_namespace: Some("files".into()),
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/macro";
+
+     t`Open`;
+     "#,
+    r#"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "files.Open",
+       message: "⟦files.Open⟧ Open"
+     });
+     "#
+);
+
+to!(
+    jsx_should_add_visible_debug_markers,
+    crate::LinguiOptions {
+        debug_markers: Some(DebugMarkers::Visible),
+        ..Default::default()
+    },
+    r#"
+     import { Trans } from "@lingui/macro";
+
+     <Trans>Hello {name}</Trans>;
+     <Trans id="greeting">Hello</Trans>;
+     "#,
+    r#"
+     import { Trans } from "@lingui/react";
+
+     <Trans id={"Hello {name}"} message={"⟦Hello '{name}'⟧ Hello {name}"} values={{
+       name: name
+     }} />;
+     <Trans message={"⟦greeting⟧ Hello"} id="greeting" />;
+     "#
+);
+
+#[test]
+fn debug_markers_zero_width() {
+    // "A" is 0b01000001
+    assert_eq!(
+        mark("Hello", "A", DebugMarkers::ZeroWidth),
+        "\u{2060}\u{200B}\u{200C}\u{200B}\u{200B}\u{200B}\u{200B}\u{200B}\u{200C}\u{2060}Hello"
+    );
+}
+
+#[test]
+fn debug_markers_visible_escapes_icu() {
+    assert_eq!(mark("x", "it's {n} #", DebugMarkers::Visible), "⟦it''s '{n} #'⟧ x");
+    assert_eq!(mark("x", "{#}", DebugMarkers::Visible), "⟦'{#}'⟧ x");
+    assert_eq!(mark("x", "greeting", DebugMarkers::Visible), "⟦greeting⟧ x");
+}
+
+#[test]
+fn debug_markers_visible_id_renders_as_is() {
+    // the runtime renders the marked message without placeholders as a plain text
+    let message = mark("Books", "{count, plural, one {# book} other {# books}}", DebugMarkers::Visible);
+
+    assert_eq!(compile_message(&message), Ok(CompiledMessage::Text(
+        "⟦{count, plural, one {# book} other {# books}}⟧ Books".into()
+    )));
+}
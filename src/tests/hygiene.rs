@@ -0,0 +1,57 @@
+use swc_core::{
+    common::SyntaxContext,
+    ecma::{
+        ast::*,
+        visit::{Visit, VisitWith},
+    },
+};
+use crate::tests::common::Transformed;
+
+/// Collect syntax contexts of `Trans` identifiers by their role
+#[derive(Default)]
+struct TransContexts {
+    imports: Vec<SyntaxContext>,
+    params: Vec<SyntaxContext>,
+    elements: Vec<SyntaxContext>,
+}
+
+impl Visit for TransContexts {
+    fn visit_import_named_specifier(&mut self, spec: &ImportNamedSpecifier) {
+        self.imports.push(spec.local.span.ctxt);
+    }
+
+    fn visit_param(&mut self, param: &Param) {
+        if let Pat::Ident(BindingIdent { id, .. }) = &param.pat {
+            self.params.push(id.span.ctxt);
+        }
+    }
+
+    fn visit_jsx_element_name(&mut self, name: &JSXElementName) {
+        if let JSXElementName::Ident(ident) = name {
+            if &ident.sym == "Trans" {
+                self.elements.push(ident.span.ctxt);
+            }
+        }
+    }
+}
+
+#[test]
+fn jsx_runtime_trans_resolves_to_injected_import() {
+    let transformed = Transformed::new(r#"import { Trans as T } from "@lingui/macro";
+function render(Trans) {
+  return <T>Hello <Trans /></T>;
+}
+"#, Default::default());
+
+    let mut contexts = TransContexts::default();
+    transformed.module.visit_with(&mut contexts);
+
+    assert_eq!(contexts.imports.len(), 1);
+    assert_eq!(contexts.params.len(), 1);
+
+    // runtime <Trans> and the local <Trans /> component
+    assert_eq!(contexts.elements.len(), 2);
+    assert_eq!(contexts.elements[0], contexts.imports[0]);
+    assert_eq!(contexts.elements[1], contexts.params[0]);
+    assert_ne!(contexts.imports[0], contexts.params[0]);
+}
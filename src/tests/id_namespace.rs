@@ -0,0 +1,125 @@
+use crate::to;
+
+to!(
+    js_should_prefix_generated_ids,
+    crate::LinguiOptions {
+        id_namespace: Some("files".into()),
+        ..Default::default()
+    },
+    r##"
+     import { t, plural, defineMessage } from "@lingui/macro";
+
+     t`Open`;
+     t`Open ${name}`;
+     plural(count, { one: "# file", other: "# files" });
+     t({ message: "Open" });
+     t({ id: "open.explicit", message: "Open" });
+     const msg = defineMessage({ message: "Open", comment: "menu item" });
+     "##,
+    r##"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "files.Open",
+       message: "Open"
+     });
+     i18n._({
+       id: "files.Open {name}",
+       message: "Open {name}",
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: "files.{count, plural, one {# file} other {# files}}",
+       message: "{count, plural, one {# file} other {# files}}",
+       values: {
+         count: count
+       }
+     });
+     i18n._({
+       id: "files.Open",
+       message: "Open"
+     });
+     i18n._({
+       id: "open.explicit",
+       message: "Open"
+     });
+     const msg = {
+       id: "files.Open",
+       message: "Open",
+       comment: "menu item"
+     };
+     "##
+);
+
+to!(
+    js_should_prefix_generated_ids_in_production,
+    crate::LinguiOptions {
+        id_namespace: Some("files".into()),
+        strip_non_essential_fields: true,
+        ..Default::default()
+    },
+    r##"
+     import { t, defineMessage } from "@lingui/macro";
+
+     t`Open ${name}`;
+     const msg = defineMessage({ message: "Open" });
+     "##,
+    r##"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "files.Open {name}",
+       values: {
+         name: name
+       }
+     });
+     const msg = {
+       id: "files.Open"
+     };
+     "##
+);
+
+to!(
+    jsx_should_prefix_generated_ids,
+    crate::LinguiOptions {
+        id_namespace: Some("files".into()),
+        ..Default::default()
+    },
+    r##"
+     import { Trans, Plural } from "@lingui/macro";
+
+     <Trans>Open</Trans>;
+     <Trans id="open.explicit">Open</Trans>;
+     <Plural value={count} one="# file" other="# files" />;
+     "##,
+    r##"
+     import { Trans } from "@lingui/react";
+
+     <Trans id={"files.Open"} message={"Open"} />;
+     <Trans message={"Open"} id="open.explicit" />;
+     <Trans id={"files.{count, plural, one {# file} other {# files}}"} message={"{count, plural, one {# file} other {# files}}"} values={{
+       count: count
+     }} />;
+     "##
+);
+
+to!(
+    jsx_should_prefix_generated_ids_in_production,
+    crate::LinguiOptions {
+        id_namespace: Some("files".into()),
+        strip_non_essential_fields: true,
+        ..Default::default()
+    },
+    r##"
+     import { Trans } from "@lingui/macro";
+
+     <Trans>Open</Trans>;
+     "##,
+    r##"
+     import { Trans } from "@lingui/react";
+
+     <Trans id={"files.Open"} />;
+     "##
+);
@@ -178,3 +178,79 @@ to!(
       i18n._("Text");
     "#
 );
+
+to!(
+    js_local_i18n_should_not_shadow_runtime_import,
+     r#"
+       import { t } from "@lingui/macro";
+
+       function render(i18n) {
+         return t`Test` + i18n.locale;
+       }
+     "#,
+    r#"
+       import { i18n } from "@lingui/core";
+
+       function render(i18n1) {
+         return i18n._("Test") + i18n1.locale;
+       }
+     "#
+);
+
+to!(
+    should_reuse_aliased_runtime_imports,
+     r#"
+       import { t, Trans } from "@lingui/macro";
+       import { i18n as core } from "@lingui/core";
+       import { Trans as TransComponent } from "@lingui/react";
+
+       const i18n = "local";
+       t`Test`;
+       <Trans>Hello</Trans>;
+     "#,
+    r#"
+       import { i18n as core } from "@lingui/core";
+       import { Trans as TransComponent } from "@lingui/react";
+
+       const i18n = "local";
+       core._("Test");
+       <TransComponent id={"Hello"} />;
+     "#
+);
+
+to!(
+    should_add_missing_specifier_to_existing_import,
+     r#"
+       import { t, Trans } from "@lingui/macro";
+       import { setupI18n } from "@lingui/core";
+       import { I18nProvider } from "@lingui/react";
+
+       t`Test`;
+       <Trans>Hello</Trans>;
+     "#,
+    r#"
+       import { setupI18n, i18n } from "@lingui/core";
+       import { I18nProvider, Trans } from "@lingui/react";
+
+       i18n._("Test");
+       <Trans id={"Hello"} />;
+     "#
+);
+
+to!(
+    should_not_add_specifier_to_namespace_or_type_imports,
+     r#"
+       import { t } from "@lingui/macro";
+       import * as core from "@lingui/core";
+       import type { I18n } from "@lingui/core";
+
+       t`Test`;
+     "#,
+    r#"
+       import { i18n } from "@lingui/core";
+       import * as core from "@lingui/core";
+       import type { I18n } from "@lingui/core";
+
+       i18n._("Test");
+     "#
+);
@@ -194,3 +194,110 @@ to!(
         });
     "#
 );
+
+to!(
+    js_fold_constants,
+    crate::LinguiOptions {
+        fold_constants: true,
+        ..Default::default()
+    },
+     r#"
+        import { t } from "@lingui/macro";
+        const VERSION = "2.0";
+        export const NAME = `App ${VERSION}`;
+        let mutable = "mutable";
+
+        t`Version ${"2.0"}`;
+        t`${3} items, ${-1.5} degrees`;
+        t`Welcome to ${NAME}, ${`nested ${"template"}`}`;
+        t`Not folded ${mutable} ${"{braces}"}`;
+        t`Zero ${-0}, small ${0.000001}`;
+        t`Not folded ${1e-7} ${1e21}`;
+
+        function scope(VERSION) {
+          t`Shadowed ${VERSION}`;
+        }
+     "#,
+    r#"
+        import { i18n } from "@lingui/core";
+        const VERSION = "2.0";
+        export const NAME = `App ${VERSION}`;
+        let mutable = "mutable";
+
+        i18n._("Version 2.0");
+        i18n._("3 items, -1.5 degrees");
+        i18n._("Welcome to App 2.0, nested template");
+        i18n._("Not folded {mutable} {0}", {
+          mutable: mutable,
+          0: "{braces}"
+        });
+        i18n._("Zero 0, small 0.000001");
+        i18n._("Not folded {0} {1}", {
+          0: 1e-7,
+          1: 1e21
+        });
+
+        function scope(VERSION) {
+          i18n._("Shadowed {VERSION}", {
+            VERSION: VERSION
+          });
+        }
+    "#
+);
+
+to!(
+    js_should_keep_configured_fields_when_stripping,
+    crate::LinguiOptions {
+        strip_non_essential_fields: true,
+        keep_fields: vec!["message".into()],
+        ..Default::default()
+    },
+    r#"
+        import { t } from '@lingui/macro'
+        const msg = t({
+            message: `Hello ${name}`,
+            id: 'msgId',
+            comment: 'description for translators',
+        })
+    "#,
+     r#"
+         import { i18n } from "@lingui/core";
+         const msg = i18n._({
+          message: "Hello {name}",
+          values: {
+            name: name,
+          },
+          id: 'msgId',
+         });
+     "#
+);
+
+to!(
+    js_should_not_fold_constants_with_icu_syntax,
+    crate::LinguiOptions {
+        fold_constants: true,
+        ..Default::default()
+    },
+     r##"
+        import { t, plural } from "@lingui/macro";
+        const Q = "'";
+        const TAG = "#1";
+
+        t`${Q}${name}`;
+        plural(count, { one: `${TAG} book`, other: "books" });
+     "##,
+    r##"
+        import { i18n } from "@lingui/core";
+        const Q = "'";
+        const TAG = "#1";
+
+        i18n._("{Q}{name}", {
+          Q: Q,
+          name: name
+        });
+        i18n._("{count, plural, one {{TAG} book} other {books}}", {
+          count: count,
+          TAG: TAG
+        });
+    "##
+);
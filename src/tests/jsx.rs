@@ -389,3 +389,228 @@ to!(
 //       `,
 //   },
 
+
+to!(
+    jsx_preserve_key_and_spreads,
+     r#"
+       import { Trans } from "@lingui/macro";
+       items.map((item) => <Trans key={item.id} {...props} data-testid="hello">Hello {item.name}</Trans>);
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       items.map((item) => <Trans key={item.id} {...props} id={"Hello {0}"} values={{
+          0: item.name
+       }} />);
+    "#
+);
+
+to!(
+    jsx_attrs_keep_source_order_around_spreads,
+    crate::LinguiOptions {
+        jsx_passthrough_attrs: vec!["xml:lang".into()],
+        ..Default::default()
+    },
+     r##"
+       import { Trans } from "@lingui/macro";
+       <Trans key="k" {...props} render={r} xml:lang="en" xlink:href="#">Hello</Trans>;
+     "##,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans key="k" {...props} id={"Hello"} render={r} xml:lang="en" />;
+    "#
+);
+
+to!(
+    jsx_spread_does_not_override_generated_attrs,
+     r#"
+       import { Trans } from "@lingui/macro";
+       <Trans {...{ id: "other", values: {} }}>Hello {name}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans {...{
+          id: "other",
+          values: {}
+       }} id={"Hello {name}"} values={{
+          name: name
+       }} />;
+    "#
+);
+
+to!(
+    jsx_passthrough_configured_attrs,
+    crate::LinguiOptions {
+        jsx_passthrough_attrs: vec!["data-*".into(), "className".into()],
+        ..Default::default()
+    },
+     r#"
+       import { Trans } from "@lingui/macro";
+       <Trans data-testid="hello" className="title" style={style}>Hello</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans id={"Hello"} data-testid="hello" className="title" />;
+    "#
+);
+
+to!(
+    production,
+    production_passthrough_attrs_are_kept,
+     r#"
+        import { Trans } from '@lingui/macro';
+        <Trans key="greeting" comment="Hello World" {...props}>Hello</Trans>
+     "#,
+
+    r#"
+        import { Trans } from "@lingui/react";
+        <Trans key="greeting" {...props} id={"Hello"} />;
+    "#
+);
+
+to!(
+    jsx_conditionals_are_placeholders_by_default,
+     r#"
+       import { Trans } from "@lingui/macro";
+       <Trans>You are {isAdmin ? "an admin" : "a guest"}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans id={"You are {0}"} values={{
+          0: isAdmin ? "an admin" : "a guest"
+       }} />;
+    "#
+);
+
+to!(
+    jsx_lift_conditionals_to_select,
+    crate::LinguiOptions {
+        lift_jsx_conditionals: true,
+        ..Default::default()
+    },
+     r#"
+       import { Trans } from "@lingui/macro";
+       <Trans>You are {isAdmin ? <b>an admin</b> : "a guest"}</Trans>;
+       <Trans>Hello{!user.isGuest && <em> and welcome</em>}!</Trans>;
+       <Trans>{count > 1 ? `${count} items` : null}</Trans>;
+       <Trans>{a ? "first" : b ? "second" : "third"}</Trans>;
+       <Trans>Value {isAdmin ? role : name}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans id={"You are {0, select, true {<0>an admin</0>} other {a guest}}"} values={{
+          0: !!isAdmin
+       }} components={{
+          0: <b />
+       }} />;
+       <Trans id={"Hello{0, select, true {<0> and welcome</0>} other {}}!"} values={{
+          0: !user.isGuest
+       }} components={{
+          0: <em />
+       }} />;
+       <Trans id={"{0, select, true {{count} items} other {}}"} values={{
+          count: count,
+          0: count > 1
+       }} />;
+       <Trans id={"{0, select, true {first} other {{1, select, true {second} other {third}}}}"} values={{
+          0: !!a,
+          1: !!b
+       }} />;
+       <Trans id={"Value {0}"} values={{
+          0: isAdmin ? role : name
+       }} />;
+    "#
+);
+
+to!(
+    jsx_lift_conditionals_keeps_non_boolean_and_as_placeholder,
+    crate::LinguiOptions {
+        lift_jsx_conditionals: true,
+        ..Default::default()
+    },
+     r#"
+       import { Trans } from "@lingui/macro";
+       <Trans>Cart {items.length && "has items"}</Trans>;
+       <Trans>Cart {items.length > 0 && "has items"}</Trans>;
+       <Trans>{!a && !b && "text"}</Trans>;
+       <Trans>{a && !b && "text"}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans id={"Cart {0}"} values={{
+          0: items.length && "has items"
+       }} />;
+       <Trans id={"Cart {0, select, true {has items} other {}}"} values={{
+          0: items.length > 0
+       }} />;
+       <Trans id={"{0, select, true {text} other {}}"} values={{
+          0: !a && !b
+       }} />;
+       <Trans id={"{0}"} values={{
+          0: a && !b && "text"
+       }} />;
+    "#
+);
+
+to!(
+    jsx_fold_constants,
+    crate::LinguiOptions {
+        fold_constants: true,
+        ..Default::default()
+    },
+     r#"
+       import { Trans } from "@lingui/macro";
+       const CONSTANT = "World";
+
+       <Trans>Hello {CONSTANT}, you have {3} {`new ${"messages"}`} from {name}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       const CONSTANT = "World";
+
+       <Trans id={"Hello World, you have 3 new messages from {name}"} values={{
+          name: name
+       }} />;
+    "#
+);
+
+to!(
+    jsx_should_not_fold_tag_like_constants,
+    crate::LinguiOptions {
+        fold_constants: true,
+        ..Default::default()
+    },
+     r#"
+       import { Trans } from "@lingui/macro";
+       const TAG = "<0>x</0>";
+       const TEXT = "a <b> c";
+
+       <Trans>Hello {TAG} {TEXT}</Trans>;
+     "#,
+    r#"
+       import { Trans } from "@lingui/react";
+       const TAG = "<0>x</0>";
+       const TEXT = "a <b> c";
+
+       <Trans id={"Hello {TAG} {TEXT}"} values={{
+          TAG: TAG,
+          TEXT: TEXT
+       }} />;
+    "#
+);
+
+to!(
+    jsx_should_keep_configured_fields_when_stripping,
+    crate::LinguiOptions {
+        strip_non_essential_fields: true,
+        keep_fields: vec!["message".into(), "comment".into()],
+        ..Default::default()
+    },
+    r#"
+      import { Trans } from "@lingui/macro";
+      <Trans id="msg.hello" comment="Greeting" context="Home">Hello World</Trans>;
+     "#,
+    r#"
+      import { Trans } from "@lingui/react";
+      <Trans message={"Hello World"} id="msg.hello" comment="Greeting" context="Home" />;
+    "#
+);
@@ -372,3 +372,123 @@ to!(
     "#
 );
 
+
+to!(
+    jsx_icu_preserve_key_and_passthrough_attrs,
+    crate::LinguiOptions {
+        jsx_passthrough_attrs: vec!["data-testid".into()],
+        ..Default::default()
+    },
+     r#"
+       import { Plural } from "@lingui/macro";
+
+      <Plural
+       key={item.id}
+       data-testid="count"
+       value={count}
+       one="Message"
+       other="Messages"
+      />
+     "#,
+
+    r#"
+       import { Trans } from "@lingui/react";
+
+       <Trans
+           id={"{count, plural, one {Message} other {Messages}}"}
+           values={{ count: count }}
+           key={item.id}
+           data-testid="count"
+        />
+    "#
+);
+
+to!(
+    jsx_icu_fragment_as_case_value,
+     r#"
+       import { Plural } from "@lingui/macro";
+
+        <Plural
+          value={count}
+          one={<>One <strong>book</strong></>}
+          other=<>Many books</>
+        />;
+     "#,
+
+    r#"
+       import { Trans } from "@lingui/react";
+        <Trans id={"{count, plural, one {One <0>book</0>} other {Many books}}"} values={{
+          count: count
+        }} components={{
+          0: <strong />
+        }} />;
+    "#
+);
+
+to!(
+    jsx_icu_choice_call_as_case_value,
+     r#"
+       import { Plural, Select, plural, select } from "@lingui/macro";
+
+        <Plural
+          value={count}
+          one="One book"
+          other={plural(shelves, { one: "On one shelf", other: "On many shelves" })}
+        />;
+
+        <Select
+          value={gender}
+          _male={(select(role, { admin: "Admin", other: "He" }))}
+          other={("They")}
+        />;
+     "#,
+
+    r#"
+       import { Trans } from "@lingui/react";
+        <Trans id={"{count, plural, one {One book} other {{shelves, plural, one {On one shelf} other {On many shelves}}}}"} values={{
+          count: count,
+          shelves: shelves
+        }} />;
+
+        <Trans id={"{gender, select, male {{role, select, admin {Admin} other {He}}} other {They}}"} values={{
+          gender: gender,
+          role: role
+        }} />;
+    "#
+);
+
+to!(
+    jsx_icu_expression_as_case_value,
+     r#"
+       import { Plural } from "@lingui/macro";
+
+        <Plural
+          value={count}
+          one={name}
+          other={`${count} books`}
+        />;
+     "#,
+
+    r#"
+       import { Trans } from "@lingui/react";
+        <Trans id={"{count, plural, one {{name}} other {{count} books}}"} values={{
+          count: count,
+          name: name
+        }} />;
+    "#
+);
+
+to!(
+    jsx_choice_call_inside_trans,
+     r#"
+       import { Trans, plural } from "@lingui/macro";
+       <Trans>You have {plural(count, { one: "one book", other: "many books" })}</Trans>;
+     "#,
+
+    r#"
+       import { Trans } from "@lingui/react";
+       <Trans id={"You have {count, plural, one {one book} other {many books}}"} values={{
+          count: count
+       }} />;
+    "#
+);
@@ -0,0 +1,103 @@
+use crate::to;
+use crate::LinguiOptions;
+use crate::options::MessageManifest;
+use crate::tests::common::Transformed;
+
+to!(
+    should_export_sorted_message_ids,
+    LinguiOptions {
+        message_manifest: Some(MessageManifest::Export),
+        ..Default::default()
+    },
+    r#"
+     import { t, defineMessage, Trans } from "@lingui/macro";
+     const a = t`Hello ${name}`;
+     const b = defineMessage({ id: "custom.id", message: "Custom" });
+     const c = t({ message: "Bye" });
+     const d = <Trans>Hello <b>{t`Bye`}</b></Trans>;
+     const e = t`Hello ${name}`;
+     "#,
+    r#"
+     import { Trans } from "@lingui/react";
+     import { i18n } from "@lingui/core";
+     const a = i18n._("Hello {name}", {
+       name: name
+     });
+     const b = {
+       id: "custom.id",
+       message: "Custom"
+     };
+     const c = i18n._({
+       id: "Bye"
+     });
+     const d = <Trans id={"Hello <0>{0}</0>"} values={{
+       0: i18n._("Bye")
+     }} components={{
+       0: <b />
+     }} />;
+     const e = i18n._("Hello {name}", {
+       name: name
+     });
+     export const __linguiMessageIds = [
+       "Bye",
+       "Hello <0>{0}</0>",
+       "Hello {name}",
+       "custom.id"
+     ];
+     "#
+);
+
+to!(
+    should_not_add_manifest_without_messages,
+    LinguiOptions {
+        message_manifest: Some(MessageManifest::Export),
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/macro";
+     const a = 1;
+     "#,
+    r#"
+     const a = 1;
+     "#
+);
+
+// comments are dropped by the `to!` harness
+
+#[test]
+fn should_add_message_ids_comment() {
+    let code = Transformed::new(r#"import { t } from "@lingui/macro";
+const a = t`Hello */ ${name}`;
+const b = t`"Quoted"`;
+"#, LinguiOptions {
+        message_manifest: Some(MessageManifest::Comment),
+        ..Default::default()
+    }).print();
+
+    assert_eq!(code, r#"/*@lingui-message-ids ["\"Quoted\"","Hello *\/ {name}"]*/ import { i18n } from "@lingui/core";
+const a = i18n._("Hello */ {name}", {
+    name: name
+});
+const b = i18n._('"Quoted"');
+"#);
+}
+
+#[test]
+fn should_add_comment_instead_of_export_in_server_modules() {
+    let code = Transformed::new(r#""use server";
+import { t } from "@lingui/macro";
+export async function greet() {
+    return t`Hello`;
+}
+"#, LinguiOptions {
+        message_manifest: Some(MessageManifest::Export),
+        ..Default::default()
+    }).print();
+
+    assert_eq!(code, r#"/*@lingui-message-ids ["Hello"]*/ "use server";
+import { i18n } from "@lingui/core";
+export async function greet() {
+    return i18n._("Hello");
+}
+"#);
+}
@@ -1,4 +1,4 @@
-mod common;
+pub(crate) mod common;
 mod js_t;
 mod js_icu;
 mod jsx;
@@ -6,3 +6,16 @@ mod jsx_icu;
 mod js_define_message;
 mod imports;
 mod runtime_config;
+mod id_namespace;
+mod pseudo;
+mod debug_markers;
+mod compile_messages;
+mod spans;
+mod comments;
+mod pure;
+mod hygiene;
+mod origin;
+mod bidi_isolation;
+mod unicode;
+mod message_manifest;
+mod process_transform;
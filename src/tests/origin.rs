@@ -0,0 +1,90 @@
+use crate::LinguiOptions;
+use crate::tests::common::Transformed;
+
+// origin is resolved through the source map, which is not set up by the `to!` harness
+
+#[test]
+fn js_macro_origin() {
+    let code = Transformed::new(r##"import { t, plural, defineMessage } from "@lingui/macro";
+const a = t`Hello ${name}`;
+  const b = plural(count, { one: "# book", other: "# books" });
+const c = defineMessage({ message: "Hi" });
+const d = t({ message: "Bye" });
+"##, LinguiOptions {
+        add_origin: true,
+        ..Default::default()
+    }).print();
+
+    assert_eq!(code, r##"import { i18n } from "@lingui/core";
+const a = i18n._({
+    id: "Hello {name}",
+    values: {
+        name: name
+    },
+    origin: [
+        "src/App.js",
+        2,
+        10
+    ]
+});
+const b = i18n._({
+    id: "{count, plural, one {# book} other {# books}}",
+    values: {
+        count: count
+    },
+    origin: [
+        "src/App.js",
+        3,
+        12
+    ]
+});
+const c = {
+    id: "Hi",
+    origin: [
+        "src/App.js",
+        4,
+        10
+    ]
+};
+const d = i18n._({
+    id: "Bye",
+    origin: [
+        "src/App.js",
+        5,
+        10
+    ]
+});
+"##);
+}
+
+#[test]
+fn jsx_macro_origin() {
+    let code = Transformed::new(r##"import { Trans } from "@lingui/macro";
+const a = (
+  <div>
+    <Trans>Hello</Trans>
+  </div>
+);
+"##, LinguiOptions {
+        add_origin: true,
+        strip_non_essential_fields: true,
+        ..Default::default()
+    }).print();
+
+    assert!(code.contains(r##"<Trans id={"Hello"} origin={[
+    "src/App.js",
+    4,
+    4
+]}/>"##), "{}", code);
+}
+
+#[test]
+fn origin_is_disabled_by_default() {
+    let code = Transformed::new(r##"import { t } from "@lingui/macro";
+t`Hello`;
+"##, Default::default()).print();
+
+    assert_eq!(code, r##"import { i18n } from "@lingui/core";
+i18n._("Hello");
+"##);
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use swc_core::ecma::ast::Program;
+use crate::transform_program;
+use crate::tests::common::{diagnostics, init_handler, TempDir, Transformed};
+
+const CODE: &str = r#"import { t } from "@lingui/macro";
+t`Hello`;
+"#;
+
+/// Transform as the plugin entry does, `path` is relative to the cwd
+fn transform(code: &str, plugin_config: &str, cwd: &str, path: &str, env_name: &str) -> String {
+    init_handler();
+
+    Transformed::with_fold(code, |module, _, _| {
+        match transform_program(Program::Module(module), plugin_config, cwd, Some(path.into()), env_name, |folder, _| folder) {
+            Program::Module(module) => module,
+            Program::Script(_) => unreachable!(),
+        }
+    }).print()
+}
+
+#[test]
+fn excluded_file_is_left_untouched() {
+    let config = r#"{ "exclude": ["**/__fixtures__/**"] }"#;
+
+    assert_eq!(transform(CODE, config, "/project", "src/__fixtures__/App.js", "development"), CODE);
+    assert_eq!(transform(CODE, config, "/project", "src/App.js", "development"), r#"import { i18n } from "@lingui/core";
+i18n._("Hello");
+"#);
+}
+
+#[test]
+fn env_options_are_applied() {
+    let config = r#"{
+        "env": { "staging": { "runtimeModules": { "i18n": ["./staging-i18n", "i18n"] } } }
+    }"#;
+
+    assert_eq!(transform(CODE, config, "/project", "src/App.js", "staging"), r#"import { i18n } from "./staging-i18n";
+i18n._("Hello");
+"#);
+}
+
+#[test]
+fn lingui_config_is_picked_up() {
+    let cwd = TempDir::new("lingui-config-is-picked-up");
+    fs::write(cwd.path().join("lingui.config.json"), r#"{
+        "locales": ["en", "cs"],
+        "sourceLocale": "en",
+        "runtimeConfigModule": ["./config-i18n", "i18n"]
+    }"#).unwrap();
+
+    assert_eq!(transform(CODE, r#"{ "linguiConfig": true }"#, cwd.path().to_str().unwrap(), "src/App.js", "development"), r#"import { i18n } from "./config-i18n";
+i18n._("Hello");
+"#);
+}
+
+#[test]
+fn unknown_key_is_reported_and_program_is_left_untouched() {
+    let code = transform(CODE, r#"{ "runtimeModule": {} }"#, "/project", "src/App.js", "development");
+
+    assert_eq!(code, CODE);
+    assert!(
+        diagnostics().iter().any(|message| message.contains("unknown field `runtimeModule`")),
+        "{:?}", diagnostics()
+    );
+}
+
+#[test]
+fn pseudo_env_keeps_message_with_stripping_by_default() {
+    let config = r#"{ "env": { "pseudo": { "pseudoLocalization": true } } }"#;
+
+    assert_eq!(transform(r#"import { t, Trans } from "@lingui/macro";
+t`Hello`;
+<Trans>Hello</Trans>;
+"#, config, "/project", "src/App.js", "pseudo"), r#"import { Trans } from "@lingui/react";
+import { i18n } from "@lingui/core";
+i18n._({
+    id: "Hello",
+    message: "[Ĥ\xe9ļļ\xf6~~]"
+});
+<Trans id={"Hello"} message={"[Ĥ\xe9ļļ\xf6~~]"}/>;
+"#);
+}
+
+#[test]
+fn debug_markers_env_keeps_message_with_stripping_by_default() {
+    let config = r#"{ "env": { "qa": { "debugMarkers": "visible" } } }"#;
+
+    assert_eq!(transform(r#"import { t, Trans } from "@lingui/macro";
+t`Hello`;
+<Trans id="greeting">Hello</Trans>;
+"#, config, "/project", "src/App.js", "qa"), r#"import { Trans } from "@lingui/react";
+import { i18n } from "@lingui/core";
+i18n._({
+    id: "Hello",
+    message: "⟦Hello⟧ Hello"
+});
+<Trans message={"⟦greeting⟧ Hello"} id="greeting"/>;
+"#);
+}
@@ -0,0 +1,110 @@
+use crate::to;
+use crate::pseudo::{accent, text_len};
+
+to!(
+    js_should_pseudo_localize_text,
+    crate::LinguiOptions {
+        pseudo_localization: Some(Default::default()),
+        ..Default::default()
+    },
+    r##"
+     import { t, plural } from "@lingui/macro";
+
+     t`Hello ${name}`;
+     t`${name}`;
+     plural(count, { one: "# book", other: "# books" });
+     t({ id: "msg.id", message: "Tom &amp; Jerry" });
+     "##,
+    r##"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "Hello {name}",
+       message: "[Ĥ\xe9ļļ\xf6 {name}~~]",
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: "{name}",
+       message: "{name}",
+       values: {
+         name: name
+       }
+     });
+     i18n._({
+       id: "{count, plural, one {# book} other {# books}}",
+       message: "[{count, plural, one {# ƀ\xf6\xf6ķ} other {# ƀ\xf6\xf6ķš}}~~~]",
+       values: {
+         count: count
+       }
+     });
+     i18n._({
+       id: "msg.id",
+       message: "[Ţ\xf6ṁ &amp; Ĵ\xe9ŕŕ\xfd~~]"
+     });
+     "##
+);
+
+to!(
+    js_should_pseudo_localize_with_custom_expansion_and_markers,
+    crate::LinguiOptions {
+        pseudo_localization: Some(crate::options::PseudoLocalization {
+            expansion: 2.0,
+            markers: ("⟦".into(), "⟧".into()),
+        }),
+        ..Default::default()
+    },
+    r##"
+     import { select } from "@lingui/macro";
+
+     select(gender, { male: "He", female: "She", other: "They" });
+     "##,
+    r##"
+     import { i18n } from "@lingui/core";
+
+     i18n._({
+       id: "{gender, select, male {He} female {She} other {They}}",
+       message: "⟦{gender, select, male {Ĥ\xe9} female {Šĥ\xe9} other {Ţĥ\xe9\xfd}}~~~~~~~~~⟧",
+       values: {
+         gender: gender
+       }
+     });
+     "##
+);
+
+to!(
+    jsx_should_pseudo_localize_text,
+    crate::LinguiOptions {
+        pseudo_localization: Some(Default::default()),
+        ..Default::default()
+    },
+    r##"
+     import { Trans } from "@lingui/macro";
+
+     <Trans>Read the <a href="/docs">docs</a>, {name}</Trans>;
+     "##,
+    r##"
+     import { Trans } from "@lingui/react";
+
+     <Trans id={"Read the <0>docs</0>, {name}"} message={"[Ŕ\xe9\xe5đ ţĥ\xe9 <0>đ\xf6\xe7š</0>, {name}~~~]"} values={{
+       name: name
+     }} components={{
+       0: <a href="/docs" />
+     }} />;
+     "##
+);
+
+#[test]
+fn pseudo_accent() {
+    assert_eq!(accent("Hello World!"), "Ĥéļļö Ŵöŕļđ!");
+    assert_eq!(accent("Tom &amp; Jerry"), "Ţöṁ &amp; Ĵéŕŕý");
+    assert_eq!(accent(r"Line\nbreak é \u{1F600}"), r"Ļîñé\nƀŕéåķ é \u{1F600}");
+}
+
+#[test]
+fn pseudo_text_len() {
+    assert_eq!(text_len("Hello, World!"), 10);
+    assert_eq!(text_len("Tom &amp; Jerry"), 8);
+    assert_eq!(text_len("  "), 0);
+}
@@ -0,0 +1,101 @@
+use swc_core::{
+    common::GLOBALS,
+    ecma::{
+        ast::Program,
+        minifier::{
+            optimize,
+            option::{ExtraOptions, MinifyOptions, terser::TerserCompressorOptions},
+        },
+    },
+};
+use crate::LinguiOptions;
+use crate::tests::common::Transformed;
+
+fn pure_options() -> LinguiOptions {
+    LinguiOptions {
+        pure_annotations: true,
+        ..Default::default()
+    }
+}
+
+fn minify(mut transformed: Transformed) -> String {
+    let compress = serde_json::from_str::<TerserCompressorOptions>(
+        r#"{ "toplevel": true, "unused": true, "module": true }"#
+    ).unwrap();
+
+    let program = GLOBALS.set(&transformed.globals, || {
+        optimize(
+            Program::Module(transformed.module.clone()),
+            transformed.cm.clone(),
+            Some(&transformed.comments),
+            None,
+            &MinifyOptions {
+                compress: Some(compress.into_config(transformed.cm.clone())),
+                ..Default::default()
+            },
+            &ExtraOptions {
+                unresolved_mark: transformed.unresolved_mark,
+                top_level_mark: transformed.top_level_mark,
+            },
+        )
+    });
+
+    transformed.module = program.expect_module();
+    transformed.print()
+}
+
+#[test]
+fn runtime_calls_are_annotated_as_pure() {
+    let code = r#"import { t } from "@lingui/macro";
+export const a = t`Hello ${name}`;
+"#;
+
+    assert_eq!(Transformed::new(code, pure_options()).print(), r#"import { i18n } from "@lingui/core";
+export const a = /*#__PURE__*/ i18n._("Hello {name}", {
+    name: name
+});
+"#);
+
+    assert!(!Transformed::new(code, Default::default()).print().contains("__PURE__"));
+}
+
+#[test]
+fn unused_messages_are_removed_by_minifier() {
+    let code = r#"import { t, plural, defineMessage } from "@lingui/macro";
+const unusedCall = t`Unused ${name}`;
+const unusedPlural = plural(count, { one: "Book", other: "Books" });
+const unusedDescriptor = defineMessage({ message: "Unused descriptor" });
+export const used = t`Used`;
+export const usedDescriptor = defineMessage({ message: "Used descriptor" });
+"#;
+
+    let minified = minify(Transformed::new(code, pure_options()));
+
+    assert!(!minified.contains("Unused"), "{minified}");
+    assert!(!minified.contains("Books"), "{minified}");
+    assert!(minified.contains("\"Used\""), "{minified}");
+    assert!(minified.contains("\"Used descriptor\""), "{minified}");
+
+    // without annotations calls are kept as side effects, unused descriptor is dropped anyway
+    let minified = minify(Transformed::new(code, Default::default()));
+    assert!(minified.contains("Unused {name}"), "{minified}");
+    assert!(minified.contains("Books"), "{minified}");
+    assert!(!minified.contains("Unused descriptor"), "{minified}");
+}
+
+#[test]
+fn macro_in_call_chain_is_not_annotated() {
+    let code = r#"import { t } from "@lingui/macro";
+t`Hello`.split("").forEach((c) => console.log(c));
+export const a = t`Used`;
+"#;
+
+    let transformed = Transformed::new(code, pure_options());
+    assert_eq!(transformed.print(), r#"import { i18n } from "@lingui/core";
+i18n._("Hello").split("").forEach((c)=>console.log(c));
+export const a = /*#__PURE__*/ i18n._("Used");
+"#);
+
+    let minified = minify(transformed);
+    assert!(minified.contains("console.log"), "{minified}");
+}
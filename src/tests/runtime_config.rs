@@ -1,32 +1,14 @@
-#[cfg(test)]
-use crate::{LinguiOptions, RuntimeModulesConfigMapNormalized};
-
-macro_rules! to {
-    ($name:ident, $options:expr, $from:expr, $to:expr) => {
-        swc_core::ecma::transforms::testing::test!(
-            swc_core::ecma::parser::Syntax::Typescript(swc_core::ecma::parser::TsConfig {
-                tsx: true,
-                ..Default::default()
-            }),
-            |_| {
-              $crate::LinguiMacroFolder::new($options)
-            },
-            $name,
-            $from,
-            $to,
-            ok_if_code_eq
-        );
-    };
-}
-
-
+use std::collections::HashMap;
+use crate::to;
+use crate::{LinguiOptions, RuntimeExport, RuntimeModulesConfigMapNormalized};
 
 to!(
     should_use_provided_runtime_modules,
     LinguiOptions {
         runtime_modules: RuntimeModulesConfigMapNormalized {
             i18n: ("./custom-core".into(), "customI18n".into()),
-            trans: ("./custom-react".into(), "CustomTrans".into())
+            trans: ("./custom-react".into(), "CustomTrans".into()),
+            i18n_getter: false,
         },
         ..Default::default()
     },
@@ -44,3 +26,132 @@ to!(
     const exp2 = <CustomTrans message={"Refresh inbox"} id="custom.id"/>;
     "#
 );
+
+to!(
+    should_use_default_export_of_runtime_modules,
+    LinguiOptions {
+        runtime_modules: RuntimeModulesConfigMapNormalized {
+            i18n: ("./i18n".into(), RuntimeExport::Default),
+            trans: ("./trans".into(), RuntimeExport::Default),
+            i18n_getter: false,
+        },
+        ..Default::default()
+    },
+     r#"
+     import { t, Trans } from "@lingui/macro";
+     import { locales } from "./i18n";
+
+     t`Refresh inbox`;
+     const exp2 = <Trans>Refresh inbox</Trans>;
+     "#,
+    r#"
+    import Trans from "./trans";
+    import i18n, { locales } from "./i18n";
+
+    i18n._("Refresh inbox");
+    const exp2 = <Trans id={"Refresh inbox"}/>;
+    "#
+);
+
+to!(
+    should_use_namespace_export_of_runtime_modules,
+    LinguiOptions {
+        runtime_modules: RuntimeModulesConfigMapNormalized {
+            i18n: ("./i18n".into(), RuntimeExport::Namespace("i18n".into())),
+            trans: ("./trans".into(), RuntimeExport::Namespace("Trans".into())),
+            i18n_getter: false,
+        },
+        ..Default::default()
+    },
+     r#"
+     import { t, Trans } from "@lingui/macro";
+
+     t`Refresh inbox`;
+     const exp2 = <Trans>Refresh inbox</Trans>;
+     "#,
+    r#"
+    import * as TransModule from "./trans";
+    import * as i18nModule from "./i18n";
+
+    i18nModule.i18n._("Refresh inbox");
+    const exp2 = <TransModule.Trans id={"Refresh inbox"}/>;
+    "#
+);
+
+to!(
+    should_reuse_existing_namespace_import,
+    LinguiOptions {
+        runtime_modules: RuntimeModulesConfigMapNormalized {
+            i18n: ("./i18n".into(), RuntimeExport::Namespace("i18n".into())),
+            trans: ("@lingui/react".into(), "Trans".into()),
+            i18n_getter: false,
+        },
+        ..Default::default()
+    },
+     r#"
+     import { t } from "@lingui/macro";
+     import * as app from "./i18n";
+
+     t`Refresh inbox`;
+     "#,
+    r#"
+    import * as app from "./i18n";
+
+    app.i18n._("Refresh inbox");
+    "#
+);
+
+to!(
+    should_use_runtime_modules_of_directive,
+    LinguiOptions {
+        directive_runtime_modules: HashMap::from([
+            ("use server".into(), RuntimeModulesConfigMapNormalized {
+                i18n: ("./i18n-server".into(), "getI18n".into()),
+                trans: ("@lingui/react/server".into(), "Trans".into()),
+                i18n_getter: true,
+            }),
+        ]),
+        ..Default::default()
+    },
+     r#"
+     "use server";
+     import { t, Trans } from "@lingui/macro";
+
+     t`Refresh inbox`;
+     const exp2 = <Trans>Refresh inbox</Trans>;
+     "#,
+    r#"
+    "use server";
+    import { Trans } from "@lingui/react/server";
+    import { getI18n } from "./i18n-server";
+
+    getI18n()._("Refresh inbox");
+    const exp2 = <Trans id={"Refresh inbox"}/>;
+    "#
+);
+
+to!(
+    should_use_default_runtime_modules_without_directive,
+    LinguiOptions {
+        directive_runtime_modules: HashMap::from([
+            ("use server".into(), RuntimeModulesConfigMapNormalized {
+                i18n: ("./i18n-server".into(), "getI18n".into()),
+                trans: ("@lingui/react/server".into(), "Trans".into()),
+                i18n_getter: true,
+            }),
+        ]),
+        ..Default::default()
+    },
+     r#"
+     "use client";
+     import { t } from "@lingui/macro";
+
+     t`Refresh inbox`;
+     "#,
+    r#"
+    "use client";
+    import { i18n } from "@lingui/core";
+
+    i18n._("Refresh inbox");
+    "#
+);
@@ -0,0 +1,98 @@
+use swc_core::{
+    common::{SourceMap, Span, sync::Lrc},
+    ecma::{
+        ast::*,
+        visit::{Visit, VisitWith},
+    },
+};
+use crate::tests::common::Transformed;
+
+/// Collect spans of the nodes generated by the plugin
+#[derive(Default)]
+struct GeneratedSpans {
+    calls: Vec<Span>,
+    objects: Vec<Span>,
+    jsx_elements: Vec<Span>,
+    jsx_attrs: Vec<Span>,
+}
+
+impl Visit for GeneratedSpans {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        self.calls.push(call.span);
+        call.visit_children_with(self);
+    }
+
+    fn visit_object_lit(&mut self, obj: &ObjectLit) {
+        self.objects.push(obj.span);
+        obj.visit_children_with(self);
+    }
+
+    fn visit_jsx_element(&mut self, el: &JSXElement) {
+        self.jsx_elements.push(el.span);
+        el.visit_children_with(self);
+    }
+
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+        self.jsx_attrs.push(attr.span);
+        attr.visit_children_with(self);
+    }
+}
+
+fn transform(code: &str) -> (Lrc<SourceMap>, GeneratedSpans) {
+    let transformed = Transformed::new(code, Default::default());
+
+    let mut spans = GeneratedSpans::default();
+    transformed.module.visit_with(&mut spans);
+
+    (transformed.cm, spans)
+}
+
+fn line_col(cm: &SourceMap, span: Span) -> (usize, usize) {
+    assert!(!span.is_dummy(), "generated node has a dummy span");
+
+    let loc = cm.lookup_char_pos(span.lo);
+    (loc.line, loc.col_display)
+}
+
+#[test]
+fn js_macro_spans_map_to_call_sites() {
+    let (cm, spans) = transform(r##"import { t, plural, defineMessage } from "@lingui/macro";
+const a = t`Hello ${name}`;
+  const b = plural(count, { one: "# book", other: "# books" });
+const c = defineMessage({ message: "Hi" });
+const d = t({ message: "Bye" });
+"##);
+
+    let calls: Vec<(usize, usize)> = spans.calls.iter().map(|span| line_col(&cm, *span)).collect();
+    assert_eq!(calls, vec![(2, 10), (3, 12), (5, 10)]);
+
+    // values of t`` and plural map to the template and choice cases,
+    // descriptors of defineMessage and t({})
+    let objects: Vec<(usize, usize)> = spans.objects.iter().map(|span| line_col(&cm, *span)).collect();
+    assert_eq!(objects, vec![(2, 11), (3, 26), (4, 10), (5, 12)]);
+}
+
+#[test]
+fn jsx_macro_spans_map_to_element() {
+    let (cm, spans) = transform(r#"import { Trans } from "@lingui/macro";
+const a = (
+  <div>
+    <Trans>Hello <b>{name}</b></Trans>
+  </div>
+);
+"#);
+
+    let elements: Vec<(usize, usize)> = spans.jsx_elements.iter().map(|span| line_col(&cm, *span)).collect();
+    // <div>, <Trans> and <b> component
+    assert_eq!(elements, vec![(3, 2), (4, 4), (4, 17)]);
+
+    // generated attributes and values map to the tag name
+    for span in spans.jsx_attrs {
+        assert_eq!(line_col(&cm, span), (4, 5));
+    }
+
+    assert_eq!(spans.objects.len(), 2);
+    for span in spans.objects {
+        assert_eq!(line_col(&cm, span), (4, 5));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::to;
+use crate::LinguiOptions;
+
+to!(
+    js_should_normalize_text_to_nfc,
+    LinguiOptions {
+        normalize_unicode: true,
+        ..Default::default()
+    },
+    // "Café" typed in NFD and NFC forms
+    "
+     import { t } from \"@lingui/macro\";
+     t`Cafe\u{301} ${name}`;
+     t`Caf\u{e9} ${name}`;
+     ",
+    "
+     import { i18n } from \"@lingui/core\";
+     i18n._(\"Caf\\xe9 {name}\", {
+       name: name
+     });
+     i18n._(\"Caf\\xe9 {name}\", {
+       name: name
+     });
+     "
+);
+
+to!(
+    js_should_normalize_descriptor_message_to_nfc,
+    LinguiOptions {
+        normalize_unicode: true,
+        ..Default::default()
+    },
+    "
+     import { defineMessage } from \"@lingui/macro\";
+     const msg = defineMessage({ message: \"A\u{30a}ngstro\u{308}m\" });
+     ",
+    "
+     const msg = {
+       id: \"\\xc5ngstr\\xf6m\"
+     };
+     "
+);
+
+to!(
+    jsx_should_normalize_text_to_nfc,
+    LinguiOptions {
+        normalize_unicode: true,
+        ..Default::default()
+    },
+    "
+     import { Trans } from \"@lingui/macro\";
+     <Trans>Cafe\u{301} <b>Cafe\u{301}</b></Trans>;
+     ",
+    "
+     import { Trans } from \"@lingui/react\";
+     <Trans id={\"Caf\\xe9 <0>Caf\\xe9</0>\"} components={{
+       0: <b />
+     }} />;
+     "
+);
+
+to!(
+    should_keep_text_as_is_by_default,
+    "
+     import { t } from \"@lingui/macro\";
+     t`Cafe\u{301}`;
+     ",
+    "
+     import { i18n } from \"@lingui/core\";
+     i18n._(\"Cafe\u{301}\");
+     "
+);